@@ -1,3 +1,5 @@
+use std::borrow::Borrow;
+
 pub trait HasLength {
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
@@ -8,7 +10,11 @@ pub trait HasLength {
 pub trait Insertable<T> {
     fn new() -> Self;
     fn insert(&mut self, value: T);
-    fn contains(&self, value: &T) -> bool; 
+    /// Looks a value up by any form it borrows as, like `HashSet::contains`.
+    fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + PartialEq;
 }
 
 pub trait Container<T>: HasLength + Insertable<T> {}
\ No newline at end of file
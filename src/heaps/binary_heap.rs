@@ -1,46 +1,118 @@
-use crate::{container::HasLength, heaps::heap::{Heap, HeapType}};
+use std::ops::{Deref, DerefMut};
 
+use crate::{container::HasLength, heaps::heap::{AddressableHeap, HandleStore, Handles, Heap, HeapHandle, HeapOrder, HeapType, IntoSortedIter, NoHandles}};
+
+/// Keeps no handles unless built through [`BinaryHeap::addressable`].
 #[derive(Clone)]
-pub struct BinaryHeap<T: PartialOrd + Clone> {
+pub struct BinaryHeap<T: Clone, O: HeapOrder<T> = HeapType, S: HandleStore = NoHandles> {
     elements: Vec<T>,
-    h_type: HeapType,
+    handles: S,
+    h_type: O,
 }
 
+/// A [`BinaryHeap`] that hands out handles for [`AddressableHeap`].
+pub type AddressableBinaryHeap<T, O = HeapType> = BinaryHeap<T, O, Handles>;
+
 impl<T> BinaryHeap<T>
 where
     T: PartialOrd + Clone,
 {
     pub fn new(heap_type: HeapType) -> Self {
-        Self {
-            elements: vec![],
-            h_type: heap_type,
-        }
+        Self::with_order(heap_type)
     }
 
     pub fn build_min(elements: Vec<T>) -> Self {
-        let mut result = Self {
-            elements,
-            h_type: HeapType::Min,
-        };
-        result.rebuild_heap();
-        result
-    } 
+        Self::build_with_order(elements, HeapType::Min)
+    }
 
     pub fn build_max(elements: Vec<T>) -> Self {
+        Self::build_with_order(elements, HeapType::Max)
+    }
+}
+
+impl<T, O> BinaryHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    pub fn with_order(order: O) -> Self {
+        Self::from_parts(vec![], order)
+    }
+
+    pub fn build_with_order(elements: Vec<T>, order: O) -> Self {
+        Self::from_parts(elements, order)
+    }
+}
+
+impl<T, O> AddressableBinaryHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    pub fn addressable(order: O) -> Self {
+        Self::from_parts(vec![], order)
+    }
+
+    pub fn build_addressable(elements: Vec<T>, order: O) -> Self {
+        Self::from_parts(elements, order)
+    }
+}
+
+impl<T, O, S> BinaryHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn from_parts(elements: Vec<T>, order: O) -> Self {
         let mut result = Self {
-            elements,
-            h_type: HeapType::Max,
+            elements: vec![],
+            handles: S::default(),
+            h_type: order,
         };
+        result.append_elements(elements);
         result.rebuild_heap();
         result
     }
 
+    /// Visits the values in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// Removes all values, yielding them in no particular order.
+    pub fn drain(&mut self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.handles.clear();
+        self.elements.drain(..)
+    }
+
+    /// Returns the values in the order `pop` would.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.into_sorted_iter().collect()
+    }
+
+    pub fn into_sorted_iter(self) -> IntoSortedIter<T, Self> {
+        IntoSortedIter::new(self)
+    }
+
+    fn append_elements(&mut self, elements: Vec<T>) {
+        for _ in 0..elements.len() {
+            self.handles.push();
+        }
+        self.elements.extend(elements);
+    }
+
     fn rebuild_heap(&mut self) {
         for index in (0..(self.len() >> 1)).rev() {
             self.heapify(index);
         }
     }
 
+    fn swap(&mut self, first: usize, second: usize) {
+        self.elements.swap(first, second);
+        self.handles.swap(first, second);
+    }
+
     fn heapify(&mut self, index: usize) {
         let mut temp = index;
         let left_index = (index << 1) + 1;
@@ -56,34 +128,122 @@ where
             temp = right_index;
         }
         if temp != index {
-            self.elements.swap(index, temp);
+            self.swap(index, temp);
             self.heapify(temp);
         }
     }
 
     fn fix_heap(&mut self, mut index: usize) {
-        let parent = index >> 1;
-        if (index > 0) && !self.h_type.is_correct(&self.elements[parent], &self.elements[index]) {
-            self.elements.swap(index, parent);
+        if index == 0 {
+            return;
+        }
+        let parent = (index - 1) >> 1;
+        if self.h_type.is_correct(&self.elements[index], &self.elements[parent]) {
+            self.swap(index, parent);
             index = parent;
             self.fix_heap(index);
         }
     }
+
+    // The old top's handle is released and the new value gets a fresh one.
+    fn replace_top(&mut self, value: T) -> T {
+        let last = self.len() - 1;
+        self.handles.swap(0, last);
+        self.handles.pop();
+        self.handles.push();
+        self.handles.swap(0, last);
+        let result = std::mem::replace(&mut self.elements[0], value);
+        self.heapify(0);
+        result
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        let last = self.len() - 1;
+        self.swap(index, last);
+        self.handles.pop();
+        let result = self.elements.pop().unwrap();
+        if index < self.len() {
+            self.heapify(index);
+            self.fix_heap(index);
+        }
+        result
+    }
 }
 
-impl<T> HasLength for BinaryHeap<T>
+impl<T, O, S> HasLength for BinaryHeap<T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
 {
     fn len(&self) -> usize {
         self.elements.len()
     }
 }
 
-impl<T> Heap<T> for BinaryHeap<T>
+impl<T, O, S> IntoIterator for BinaryHeap<T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
 {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a, T, O, S> IntoIterator for &'a BinaryHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, O, S> FromIterator<T> for BinaryHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T> + Default,
+    S: HandleStore,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_parts(iter.into_iter().collect(), O::default())
+    }
+}
+
+impl<T, O, S> Extend<T> for BinaryHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_from(iter);
+    }
+}
+
+impl<T, O, S> Heap<T> for BinaryHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Order = O;
+    type PeekMut<'a> = PeekMut<'a, T, O, S> where Self: 'a;
+
+    fn from_vec(elements: Vec<T>, order: O) -> Self {
+        Self::from_parts(elements, order)
+    }
+
     fn peek(&self) -> Option<&T> {
         if self.is_empty() {
             None
@@ -92,40 +252,170 @@ where
         }
     }
 
+    fn peek_mut(&mut self) -> Option<PeekMut<'_, T, O, S>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
     fn push(&mut self, value: T) {
+        self.handles.push();
         self.elements.push(value);
-        let index = self.elements.len() - 1;
-        self.fix_heap(index);
+        self.fix_heap(self.len() - 1);
     }
 
     fn pop(&mut self) -> Option<T> {
         if self.is_empty() {
             None
         } else {
-            let last = self.len() - 1;
-            self.elements.swap(0, last);
-            let result = self.elements.pop();
-            if !self.is_empty() {
-                self.heapify(0);
+            Some(self.remove_at(0))
+        }
+    }
+
+    fn push_pop(&mut self, value: T) -> T {
+        if self.is_empty() || !self.h_type.is_correct(&self.elements[0], &value) {
+            return value;
+        }
+        self.replace_top(value)
+    }
+
+    fn replace(&mut self, value: T) -> Option<T> {
+        if self.is_empty() {
+            self.push(value);
+            return None;
+        }
+        Some(self.replace_top(value))
+    }
+
+    // Floyd's bottom-up rebuild costs about two comparisons per value, so it wins once
+    // pushing the batch one by one would cost more.
+    fn extend_from<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let values: Vec<T> = values.into_iter().collect();
+        if values.is_empty() {
+            return;
+        }
+        let total = self.len() + values.len();
+        if (2 * total) < (values.len() * (total.ilog2() as usize)) {
+            self.append_elements(values);
+            self.rebuild_heap();
+        } else {
+            for value in values {
+                self.push(value);
             }
-            result
         }
     }
 
     fn meld(&mut self, other: &mut Self) {
-        self.elements.append(&mut other.elements);
+        other.handles.clear();
+        self.append_elements(std::mem::take(&mut other.elements));
         self.rebuild_heap();
     }
-    
-    fn merge(self, other: Self, new_heap_type: HeapType) -> Self {
-        let mut result = Self {
-            elements: Vec::with_capacity(self.len() + other.len()),
-            h_type: new_heap_type,
+
+    fn merge(mut self, mut other: Self, new_heap_type: O) -> Self {
+        self.h_type = new_heap_type;
+        self.meld(&mut other);
+        self
+    }
+}
+
+impl<T, O> AddressableHeap<T> for AddressableBinaryHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn push_with_handle(&mut self, value: T) -> HeapHandle {
+        let handle = self.handles.push_handle();
+        self.elements.push(value);
+        self.fix_heap(self.len() - 1);
+        handle
+    }
+
+    fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.handles.position(handle).map(|index| &self.elements[index])
+    }
+
+    fn update(&mut self, handle: HeapHandle, value: T) -> bool {
+        let Some(index) = self.handles.position(handle) else {
+            return false;
         };
-        result.elements.extend(self.elements.into_iter());
-        result.elements.extend(other.elements.into_iter());
+        self.elements[index] = value;
+        self.heapify(index);
+        self.fix_heap(index);
+        true
+    }
 
-        result.rebuild_heap();
-        result
+    fn remove(&mut self, handle: HeapHandle) -> Option<T> {
+        let index = self.handles.position(handle)?;
+        Some(self.remove_at(index))
+    }
+}
+
+/// Sifts the top value down when dropped, keeping its handle.
+pub struct PeekMut<'a, T: Clone, O: HeapOrder<T>, S: HandleStore> {
+    heap: &'a mut BinaryHeap<T, O, S>,
+}
+
+impl<T, O, S> Deref for PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.elements[0]
     }
-}
\ No newline at end of file
+}
+
+impl<T, O, S> DerefMut for PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.elements[0]
+    }
+}
+
+impl<T, O, S> Drop for PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn drop(&mut self) {
+        self.heap.heapify(0);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, O, S> serde::Serialize for BinaryHeap<T, O, S>
+where
+    T: Clone + serde::Serialize,
+    O: HeapOrder<T> + serde::Serialize,
+    S: HandleStore,
+{
+    fn serialize<Se: serde::Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        crate::heaps::heap::SerializedHeapRef {
+            order: &self.h_type,
+            elements: self.elements.iter().collect(),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, O, S> serde::Deserialize<'de> for BinaryHeap<T, O, S>
+where
+    T: Clone + serde::Deserialize<'de>,
+    O: HeapOrder<T> + serde::Deserialize<'de>,
+    S: HandleStore,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let heap = crate::heaps::heap::SerializedHeap::deserialize(deserializer)?;
+        Ok(Self::from_parts(heap.elements, heap.order))
+    }
+}
@@ -1,50 +1,170 @@
-use crate::{container::HasLength, heaps::heap::{Heap, HeapType}};
+use std::ops::{Deref, DerefMut};
 
+use crate::{container::HasLength, heaps::heap::{AddressableHeap, HandleStore, Handles, Heap, HeapHandle, HeapOrder, HeapType, IntoSortedIter, NoHandles}};
+
+/// Keeps every node in one arena with links to its parent, so a handle finds its value
+/// directly and moves it in O(log n) steps. Keeps no handles unless built through
+/// [`BinomialHeap::addressable`].
 #[derive(Clone)]
-pub struct BinomialHeap<T: PartialOrd + Clone> {
+pub struct BinomialHeap<T: Clone, O: HeapOrder<T> = HeapType, S: HandleStore = NoHandles> {
     pointer: usize,
-    h_type: HeapType,
-    length: usize,
-    trees: Vec<Option<Box<BinomialTree<T>>>>,
+    h_type: O,
+    nodes: Vec<BinomialNode<T>>,
+    trees: Vec<Option<usize>>,
+    handles: S,
 }
 
+/// A [`BinomialHeap`] that hands out handles for [`AddressableHeap`].
+pub type AddressableBinomialHeap<T, O = HeapType> = BinomialHeap<T, O, Handles>;
+
 impl<T> BinomialHeap<T>
 where
     T: PartialOrd + Clone,
 {
     pub fn new(heap_type: HeapType) -> Self {
+        Self::with_order(heap_type)
+    }
+}
+
+impl<T, O> BinomialHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    pub fn with_order(order: O) -> Self {
+        Self::empty(order)
+    }
+}
+
+impl<T, O> AddressableBinomialHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    pub fn addressable(order: O) -> Self {
+        Self::empty(order)
+    }
+}
+
+impl<T, O, S> BinomialHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn empty(order: O) -> Self {
         Self {
             pointer: 0,
-            h_type: heap_type,
-            length: 0,
+            h_type: order,
+            nodes: vec![],
             trees: vec![],
+            handles: S::default(),
         }
     }
 
-    fn merge_trees(
-        mut first: BinomialTree<T>,
-        mut second: BinomialTree<T>,
-        heap_type: HeapType,
-    ) -> BinomialTree<T> {
-        let mut result;
-        if heap_type.is_correct(&first.value, &second.value) {
-            first.subtrees.push(Box::new(second));
-            result = first;
+    /// Visits the values in no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: self.nodes.iter(),
+        }
+    }
+
+    /// Removes all values, yielding them in no particular order.
+    pub fn drain(&mut self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.trees.clear();
+        self.handles.clear();
+        self.pointer = 0;
+        self.nodes.drain(..).map(|node| node.value)
+    }
+
+    /// Returns the values in the order `pop` would.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.into_sorted_iter().collect()
+    }
+
+    pub fn into_sorted_iter(self) -> IntoSortedIter<T, Self> {
+        IntoSortedIter::new(self)
+    }
+
+    // Hangs the root that belongs lower under the other one and returns the new root.
+    fn link(&mut self, first: usize, second: usize) -> usize {
+        let (parent, child) = if self.h_type.is_correct(&self.nodes[first].value, &self.nodes[second].value) {
+            (first, second)
         } else {
-            second.subtrees.push(Box::new(first));
-            result = second;
+            (second, first)
+        };
+        self.nodes[child].parent = Some(parent);
+        self.nodes[parent].children.push(child);
+        parent
+    }
+
+    // Works like incrementing a binary counter: equal ranks are linked until a slot is free.
+    fn add_tree(&mut self, mut root: usize) {
+        loop {
+            let rank = self.nodes[root].children.len();
+            if rank >= self.trees.len() {
+                self.trees.resize(rank + 1, None);
+            }
+            match self.trees[rank].take() {
+                Some(other) => root = self.link(root, other),
+                None => {
+                    self.trees[rank] = Some(root);
+                    return;
+                }
+            }
         }
-        result.rank += 1;
-        result
+    }
+
+    // Pairs trees of equal rank level by level, starting from single nodes, so building
+    // `n` nodes makes fewer than `n` links.
+    fn build_trees(&mut self, mut level: Vec<usize>) {
+        let mut roots = vec![];
+        while !level.is_empty() {
+            let mut next = Vec::with_capacity(level.len() >> 1);
+            let mut pairs = level.chunks_exact(2);
+            for pair in &mut pairs {
+                next.push(self.link(pair[0], pair[1]));
+            }
+            roots.extend(pairs.remainder().first().copied());
+            level = next;
+        }
+        for root in roots {
+            self.add_tree(root);
+        }
+        self.update_pointer();
+    }
+
+    fn push_values(&mut self, values: Vec<T>) {
+        let start = self.nodes.len();
+        for value in values {
+            self.handles.push();
+            self.nodes.push(BinomialNode::from(value));
+        }
+        self.build_trees((start..self.nodes.len()).collect());
+    }
+
+    fn insert(&mut self, value: T) {
+        self.nodes.push(BinomialNode::from(value));
+        self.add_tree(self.nodes.len() - 1);
+        self.update_pointer();
+    }
+
+    fn relink(&mut self) {
+        self.trees.clear();
+        for node in self.nodes.iter_mut() {
+            node.parent = None;
+            node.children.clear();
+        }
+        self.build_trees((0..self.nodes.len()).collect());
     }
 
     fn update_pointer(&mut self) {
         let mut best_idx: Option<usize> = None;
         for (i, t) in self.trees.iter().enumerate() {
-            if let Some(tree) = t.as_ref() {
+            if let Some(&root) = t.as_ref() {
                 if let Some(bi) = best_idx {
-                    let best_val = &self.trees[bi].as_ref().unwrap().value;
-                    if self.h_type.is_correct(&tree.value, best_val) {
+                    let best_val = &self.nodes[self.trees[bi].unwrap()].value;
+                    if self.h_type.is_correct(&self.nodes[root].value, best_val) {
                         best_idx = Some(i);
                     }
                 } else {
@@ -55,276 +175,396 @@ where
         self.pointer = best_idx.unwrap_or(0);
     }
 
-    fn to_vec(&self) -> Vec<T> {
-        let mut result = Vec::with_capacity(self.len());
-        for tree in &self.trees {
-            if let Some(tree) = tree {
-                result.extend(tree.to_vec());
-            } 
+    fn swap_values(&mut self, first: usize, second: usize) {
+        let (low, high) = (first.min(second), first.max(second));
+        let (head, tail) = self.nodes.split_at_mut(high);
+        std::mem::swap(&mut head[low].value, &mut tail[0].value);
+        self.handles.swap(first, second);
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while let Some(parent) = self.nodes[index].parent
+            && self.h_type.is_correct(&self.nodes[index].value, &self.nodes[parent].value)
+        {
+            self.swap_values(index, parent);
+            index = parent;
         }
-        result
+    }
+
+    // Moves the value down past every child that belongs above it.
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let node = &self.nodes[index];
+            let best = node.children.iter().copied().reduce(|best, child| {
+                if self.h_type.is_correct(&self.nodes[child].value, &self.nodes[best].value) { child } else { best }
+            });
+            match best {
+                Some(child) if self.h_type.is_correct(&self.nodes[child].value, &node.value) => {
+                    self.swap_values(index, child);
+                    index = child;
+                },
+                _ => return,
+            }
+        }
+    }
+
+    // Moves the value at `index` up to the root of its tree, whatever its order, and returns
+    // the root.
+    fn raise(&mut self, mut index: usize) -> usize {
+        while let Some(parent) = self.nodes[index].parent {
+            self.swap_values(index, parent);
+            index = parent;
+        }
+        index
+    }
+
+    fn remove_root(&mut self, rank: usize) -> T {
+        let root = self.trees[rank].take().unwrap();
+        for child in std::mem::take(&mut self.nodes[root].children) {
+            self.nodes[child].parent = None;
+            self.add_tree(child);
+        }
+        while matches!(self.trees.last(), Some(None)) {
+            self.trees.pop();
+        }
+        let value = self.remove_node(root);
+        self.update_pointer();
+        value
+    }
+
+    // Fills the gap with the last node, so every link to that node is redirected.
+    fn remove_node(&mut self, index: usize) -> T {
+        let last = self.nodes.len() - 1;
+        self.handles.swap(index, last);
+        self.handles.pop();
+        let node = self.nodes.swap_remove(index);
+        if index != last {
+            match self.nodes[index].parent {
+                Some(parent) => {
+                    let children = &mut self.nodes[parent].children;
+                    let position = children.iter().position(|&child| child == last).unwrap();
+                    children[position] = index;
+                },
+                None => {
+                    let rank = self.nodes[index].children.len();
+                    self.trees[rank] = Some(index);
+                },
+            }
+            for position in 0..self.nodes[index].children.len() {
+                let child = self.nodes[index].children[position];
+                self.nodes[child].parent = Some(index);
+            }
+        }
+        node.value
     }
 }
 
-impl<T> HasLength for BinomialHeap<T>
+impl<T, O, S> HasLength for BinomialHeap<T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
 {
     fn len(&self) -> usize {
-        self.length
+        self.nodes.len()
     }
 }
 
-enum HeapTypeMatch {
-    All,
-    First,
-    Second,
-    None,
-}
+impl<T, O, S> IntoIterator for BinomialHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
 
-fn heap_type_match(
-    main_type: HeapType,
-    first_type: HeapType,
-    second_type: HeapType,
-) -> HeapTypeMatch {
-    if (first_type == main_type) & (second_type == main_type) {
-        HeapTypeMatch::All
-    } else if first_type == main_type {
-        HeapTypeMatch::First
-    } else if second_type == main_type {
-        HeapTypeMatch::Second
-    } else {
-        HeapTypeMatch::None
+    fn into_iter(self) -> Self::IntoIter {
+        let values: Vec<T> = self.nodes.into_iter().map(|node| node.value).collect();
+        values.into_iter()
     }
 }
 
-struct MergeBucket<T: PartialOrd + Clone> {
-    bucket: [Option<Box<BinomialTree<T>>>; 3],
-    pointer: isize,
+impl<'a, T, O, S> IntoIterator for &'a BinomialHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
-impl<T> MergeBucket<T>
-where T: PartialOrd + Clone,
+impl<T, O, S> FromIterator<T> for BinomialHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T> + Default,
+    S: HandleStore,
 {
-    fn new() -> Self {
-        Self {
-            bucket: [const { None }; 3],
-            pointer: -1,
-        }
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect(), O::default())
     }
+}
 
-    fn len(&self) -> usize {
-        (self.pointer + 1) as usize
+impl<T, O, S> Extend<T> for BinomialHeap<T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_from(iter);
     }
+}
 
-    fn push(&mut self, value: Box<BinomialTree<T>>) {
-        if self.pointer <= 1 {
-            self.pointer += 1;
-            self.bucket[self.pointer as usize] = Some(value);
-        }
+pub struct Iter<'a, T: Clone> {
+    nodes: std::slice::Iter<'a, BinomialNode<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Clone,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.nodes.next().map(|node| &node.value)
     }
 
-    fn pop(&mut self) -> Option<Box<BinomialTree<T>>> {
-        if (self.pointer >= 0) && (self.pointer < 3) && self.bucket[self.pointer as usize].is_some() {
-            let result = self.bucket[self.pointer as usize].clone();
-            self.bucket[self.pointer as usize] = None;
-            self.pointer -= 1;
-            return result
-        }
-        None
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
     }
 }
 
-impl<T> Heap<T> for BinomialHeap<T>
+impl<T> ExactSizeIterator for Iter<'_, T> where T: Clone {}
+
+impl<T, O, S> Heap<T> for BinomialHeap<T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
 {
+    type Order = O;
+    type PeekMut<'a> = PeekMut<'a, T, O, S> where Self: 'a;
+
+    fn from_vec(elements: Vec<T>, order: O) -> Self {
+        let mut result = Self::empty(order);
+        result.push_values(elements);
+        result
+    }
+
     fn peek(&self) -> Option<&T> {
         if self.is_empty() {
             None
         } else {
-            Some(&(self.trees[self.pointer].as_ref().unwrap().value))
+            Some(&self.nodes[self.trees[self.pointer].unwrap()].value)
         }
     }
 
-    fn pop(&mut self) -> Option<T> {
+    fn peek_mut(&mut self) -> Option<PeekMut<'_, T, O, S>> {
         if self.is_empty() {
-            return None;
+            None
+        } else {
+            Some(PeekMut { heap: self })
         }
-        let tree = self.trees[self.pointer].take().unwrap();
-
-        let k = tree.rank;
-        let tree_size = 1usize << k;
-        let value = tree.value;
-
-        self.length = self.length.saturating_sub(tree_size);
+    }
 
-        let mut other = BinomialHeap {
-            length: (1usize << k) - 1,
-            pointer: 0,
-            h_type: self.h_type,
-            trees: vec![None; k],
-        };
-        for child in tree.subtrees {
-            let r = child.rank;
-            other.trees[r] = Some(child);
+    fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
         }
-
-        self.meld(&mut other); 
-
-        Some(value)
+        Some(self.remove_root(self.pointer))
     }
 
     fn push(&mut self, value: T) {
-        self.meld(&mut BinomialHeap {
-            pointer: 0,
-            h_type: self.h_type,
-            length: 1,
-            trees: vec![Some(Box::new(BinomialTree::from(value)))],
-        });
+        self.handles.push();
+        self.insert(value);
     }
 
-    fn meld(&mut self, other: &mut Self) {
-        if self.h_type == other.h_type {
-            let heap_type = self.h_type;
-            let max_len = self.trees.len().max(other.trees.len());
-            self.trees.resize(max_len, None);
-            other.trees.resize(max_len, None);
-
-            let mut new_trees: Vec<Option<Box<BinomialTree<T>>>> = Vec::with_capacity(max_len + 1);
-            new_trees.resize(max_len + 1, None);
-
-            let mut carry: Option<Box<BinomialTree<T>>> = None;
-
-            for i in 0..max_len {
-                let mut bucket = MergeBucket::new();
-                if let Some(t) = self.trees[i].take() { 
-                    bucket.push(t); 
-                }
-                if let Some(t) = other.trees[i].take() { 
-                    bucket.push(t); 
-                }
-                if let Some(t) = carry.take() {
-                    bucket.push(t); 
-                }
+    fn extend_from<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        self.push_values(values.into_iter().collect());
+    }
 
-                match bucket.len() {
-                    1 => {
-                        new_trees[i] = Some(bucket.pop().unwrap());
-                    }
-                    2 => {
-                        let b = *bucket.pop().unwrap();
-                        let a = *bucket.pop().unwrap();
-                        let merged = Self::merge_trees(a, b, heap_type);
-                        carry = Some(Box::new(merged));
-                    }
-                    3 => {
-                        let keep = bucket.pop().unwrap();
-                        let b = *bucket.pop().unwrap();
-                        let a = *bucket.pop().unwrap();
-                        new_trees[i] = Some(keep);
-                        let merged = Self::merge_trees(a, b, heap_type);
-                        carry = Some(Box::new(merged));
-                    }
-                    _ => {},
+    // The other heap's nodes move into this arena, so melding takes time linear in `other`.
+    fn meld(&mut self, other: &mut Self) {
+        let offset = self.nodes.len();
+        let same_order = self.h_type.is_same_order(&other.h_type);
+        let roots: Vec<usize> = other.trees.iter().flatten().map(|root| root + offset).collect();
+        other.trees.clear();
+        other.handles.clear();
+        other.pointer = 0;
+        for mut node in other.nodes.drain(..) {
+            if same_order {
+                node.parent = node.parent.map(|parent| parent + offset);
+                for child in node.children.iter_mut() {
+                    *child += offset;
                 }
+            } else {
+                node.parent = None;
+                node.children.clear();
             }
+            self.handles.push();
+            self.nodes.push(node);
+        }
 
-            if let Some(t) = carry {
-                if new_trees.len() == max_len {
-                    new_trees.push(None);
-                }
-                new_trees[max_len] = Some(t);
+        if same_order {
+            for root in roots {
+                self.add_tree(root);
             }
+            self.update_pointer();
+        } else {
+            self.build_trees((offset..self.nodes.len()).collect());
+        }
+    }
 
-            self.trees = new_trees;
+    // Relinking keeps every node where it is, so handles into `self` stay valid.
+    fn merge(mut self, mut other: Self, new_heap_type: O) -> Self {
+        let relink = !self.h_type.is_same_order(&new_heap_type);
+        self.h_type = new_heap_type;
+        if relink {
+            self.relink();
+        }
+        self.meld(&mut other);
+        self
+    }
+}
 
-            self.length += other.length;
-            other.length = 0;
-            other.trees.clear();
+#[derive(Clone)]
+struct BinomialNode<T> {
+    value: T,
+    parent: Option<usize>,
+    // Children are linked in rising rank, so a tree's rank is its number of children.
+    children: Vec<usize>,
+}
 
-            
+impl<T> From<T> for BinomialNode<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            parent: None,
+            children: vec![],
+        }
+    }
+}
+
+impl<T, O> AddressableHeap<T> for AddressableBinomialHeap<T, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn push_with_handle(&mut self, value: T) -> HeapHandle {
+        let handle = self.handles.push_handle();
+        self.insert(value);
+        handle
+    }
+
+    fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.handles.position(handle).map(|index| &self.nodes[index].value)
+    }
+
+    fn update(&mut self, handle: HeapHandle, value: T) -> bool {
+        let Some(index) = self.handles.position(handle) else {
+            return false;
+        };
+        let old = std::mem::replace(&mut self.nodes[index].value, value);
+        if self.h_type.is_correct(&self.nodes[index].value, &old) {
+            self.sift_up(index);
         } else {
-            let vec = other.to_vec();
-            for value in vec {
-                self.push(value);
-            }
+            self.sift_down(index);
         }
         self.update_pointer();
+        true
     }
 
+    fn remove(&mut self, handle: HeapHandle) -> Option<T> {
+        let index = self.handles.position(handle)?;
+        let root = self.raise(index);
+        Some(self.remove_root(self.nodes[root].children.len()))
+    }
+}
 
-    fn merge(mut self, mut other: Self, new_heap_type: HeapType) -> Self {
-        match heap_type_match(new_heap_type, self.h_type, other.h_type) {
-            HeapTypeMatch::All => {
-                self.meld(&mut other);
-                self
-            },
-            HeapTypeMatch::First => {
-                let mut other = other.to_vec();
-                for value in other.drain(..) {
-                    self.push(value);
-                }
-                self
-            },
-            HeapTypeMatch::Second => {
-                let mut result = other;
-                let mut other = self.to_vec();
-                for value in other.drain(..) {
-                    result.push(value);
-                }
-                result
-            },
-            HeapTypeMatch::None => {
-                let mut heap = Self::new(new_heap_type);
-                let mut first = other.to_vec();
-                let mut second = self.to_vec();
-                for value in first.drain(..){
-                    heap.push(value);
-                }
-                for value in second.drain(..) {
-                    heap.push(value);
-                }
-                heap
-            },
-        }
+/// Sifts the top value down its tree when dropped, keeping its handle.
+pub struct PeekMut<'a, T: Clone, O: HeapOrder<T>, S: HandleStore> {
+    heap: &'a mut BinomialHeap<T, O, S>,
+}
+
+impl<T, O, S> PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn top(&self) -> usize {
+        self.heap.trees[self.heap.pointer].unwrap()
     }
 }
 
-#[derive(Clone)]
-struct BinomialTree<T: PartialOrd + Clone> {
-    rank: usize,
-    value: T,
-    subtrees: Vec<Box<BinomialTree<T>>>,
+impl<T, O, S> Deref for PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.nodes[self.top()].value
+    }
 }
 
-impl<T> BinomialTree<T>
+impl<T, O, S> DerefMut for PeekMut<'_, T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
 {
-    fn to_vec(&self) -> Vec<T> {
-        let mut result = Vec::with_capacity(1 << self.rank);
-        self.fill_vec(&mut result);
-        result
+    fn deref_mut(&mut self) -> &mut T {
+        let top = self.top();
+        &mut self.heap.nodes[top].value
     }
+}
 
-    fn fill_vec(&self, result: &mut Vec<T>) {
-        result.push(self.value.clone());
-        for subtree in &self.subtrees {
-            subtree.fill_vec(result);
-        }
+impl<T, O, S> Drop for PeekMut<'_, T, O, S>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+    S: HandleStore,
+{
+    fn drop(&mut self) {
+        let top = self.top();
+        self.heap.sift_down(top);
+        self.heap.update_pointer();
     }
 }
 
-impl<T> From<T> for BinomialTree<T>
+#[cfg(feature = "serde")]
+impl<T, O, S> serde::Serialize for BinomialHeap<T, O, S>
 where
-    T: PartialOrd + Clone,
+    T: Clone + serde::Serialize,
+    O: HeapOrder<T> + serde::Serialize,
+    S: HandleStore,
 {
-    fn from(value: T) -> Self {
-        Self {
-            rank: 0,
-            value,
-            subtrees: vec![],
-        }
+    fn serialize<Se: serde::Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        crate::heaps::heap::SerializedHeapRef {
+            order: &self.h_type,
+            elements: self.iter().collect(),
+        }.serialize(serializer)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'de, T, O, S> serde::Deserialize<'de> for BinomialHeap<T, O, S>
+where
+    T: Clone + serde::Deserialize<'de>,
+    O: HeapOrder<T> + serde::Deserialize<'de>,
+    S: HandleStore,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let heap = crate::heaps::heap::SerializedHeap::deserialize(deserializer)?;
+        Ok(Self::from_vec(heap.elements, heap.order))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,303 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{container::HasLength, heaps::heap::{Heap, HeapOrder, HeapType, IntoSortedIter}};
+
+/// An implicit heap where every node has `D` children. Wider nodes make the heap shallower
+/// and keep siblings in one cache line, at the cost of more comparisons per level.
+/// Unlike [`AddressableBinaryHeap`](super::binary_heap::AddressableBinaryHeap) it keeps no handles.
+#[derive(Clone)]
+pub struct DaryHeap<T: Clone, const D: usize, O: HeapOrder<T> = HeapType> {
+    elements: Vec<T>,
+    h_type: O,
+}
+
+impl<T, const D: usize> DaryHeap<T, D>
+where
+    T: PartialOrd + Clone,
+{
+    pub fn new(heap_type: HeapType) -> Self {
+        Self::with_order(heap_type)
+    }
+}
+
+impl<T, const D: usize, O> DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    pub fn with_order(order: O) -> Self {
+        const { assert!(D >= 2, "a d-ary heap needs at least two children per node") };
+        Self {
+            elements: vec![],
+            h_type: order,
+        }
+    }
+
+    pub fn build_with_order(elements: Vec<T>, order: O) -> Self {
+        let mut result = Self::with_order(order);
+        result.elements = elements;
+        result.rebuild_heap();
+        result
+    }
+
+    /// Visits the values in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// Removes all values, yielding them in no particular order.
+    pub fn drain(&mut self) -> impl ExactSizeIterator<Item = T> + '_ {
+        self.elements.drain(..)
+    }
+
+    /// Returns the values in the order `pop` would.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.into_sorted_iter().collect()
+    }
+
+    pub fn into_sorted_iter(self) -> IntoSortedIter<T, Self> {
+        IntoSortedIter::new(self)
+    }
+
+    fn rebuild_heap(&mut self) {
+        if self.len() < 2 {
+            return;
+        }
+        for index in (0..=((self.len() - 2) / D)).rev() {
+            self.heapify(index);
+        }
+    }
+
+    fn heapify(&mut self, mut index: usize) {
+        loop {
+            let first_child = (index * D) + 1;
+            let last_child = (first_child + D).min(self.len());
+            let mut best = index;
+            for child in first_child..last_child {
+                if self.h_type.is_correct(&self.elements[child], &self.elements[best]) {
+                    best = child;
+                }
+            }
+            if best == index {
+                return;
+            }
+            self.elements.swap(index, best);
+            index = best;
+        }
+    }
+
+    fn fix_heap(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / D;
+            if !self.h_type.is_correct(&self.elements[index], &self.elements[parent]) {
+                return;
+            }
+            self.elements.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn replace_top(&mut self, value: T) -> T {
+        let result = std::mem::replace(&mut self.elements[0], value);
+        self.heapify(0);
+        result
+    }
+}
+
+impl<T, const D: usize, O> HasLength for DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl<T, const D: usize, O> IntoIterator for DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a, T, const D: usize, O> IntoIterator for &'a DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const D: usize, O> FromIterator<T> for DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T> + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::build_with_order(iter.into_iter().collect(), O::default())
+    }
+}
+
+impl<T, const D: usize, O> Extend<T> for DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.extend_from(iter);
+    }
+}
+
+impl<T, const D: usize, O> Heap<T> for DaryHeap<T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    type Order = O;
+    type PeekMut<'a> = PeekMut<'a, T, D, O> where Self: 'a;
+
+    fn from_vec(elements: Vec<T>, order: O) -> Self {
+        Self::build_with_order(elements, order)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    fn peek_mut(&mut self) -> Option<PeekMut<'_, T, D, O>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let last = self.elements.pop()?;
+        if self.is_empty() {
+            Some(last)
+        } else {
+            Some(self.replace_top(last))
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        self.elements.push(value);
+        self.fix_heap(self.len() - 1);
+    }
+
+    fn push_pop(&mut self, value: T) -> T {
+        if self.is_empty() || !self.h_type.is_correct(&self.elements[0], &value) {
+            return value;
+        }
+        self.replace_top(value)
+    }
+
+    fn replace(&mut self, value: T) -> Option<T> {
+        if self.is_empty() {
+            self.push(value);
+            return None;
+        }
+        Some(self.replace_top(value))
+    }
+
+    fn extend_from<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        let values: Vec<T> = values.into_iter().collect();
+        if values.is_empty() {
+            return;
+        }
+        let total = self.len() + values.len();
+        if (2 * total) < (values.len() * (total.ilog(D) as usize)) {
+            self.elements.extend(values);
+            self.rebuild_heap();
+        } else {
+            for value in values {
+                self.push(value);
+            }
+        }
+    }
+
+    fn meld(&mut self, other: &mut Self) {
+        self.extend_from(std::mem::take(&mut other.elements));
+    }
+
+    fn merge(mut self, other: Self, new_heap_type: O) -> Self {
+        self.elements.extend(other.elements);
+        Self::build_with_order(self.elements, new_heap_type)
+    }
+}
+
+/// Sifts the top value down when dropped.
+pub struct PeekMut<'a, T: Clone, const D: usize, O: HeapOrder<T>> {
+    heap: &'a mut DaryHeap<T, D, O>,
+}
+
+impl<T, const D: usize, O> Deref for PeekMut<'_, T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.elements[0]
+    }
+}
+
+impl<T, const D: usize, O> DerefMut for PeekMut<'_, T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.elements[0]
+    }
+}
+
+impl<T, const D: usize, O> Drop for PeekMut<'_, T, D, O>
+where
+    T: Clone,
+    O: HeapOrder<T>,
+{
+    fn drop(&mut self) {
+        self.heap.heapify(0);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, const D: usize, O> serde::Serialize for DaryHeap<T, D, O>
+where
+    T: Clone + serde::Serialize,
+    O: HeapOrder<T> + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::heaps::heap::SerializedHeapRef {
+            order: &self.h_type,
+            elements: self.elements.iter().collect(),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const D: usize, O> serde::Deserialize<'de> for DaryHeap<T, D, O>
+where
+    T: Clone + serde::Deserialize<'de>,
+    O: HeapOrder<T> + serde::Deserialize<'de>,
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let heap = crate::heaps::heap::SerializedHeap::deserialize(deserializer)?;
+        Ok(Self::build_with_order(heap.elements, heap.order))
+    }
+}
\ No newline at end of file
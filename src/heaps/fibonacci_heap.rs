@@ -0,0 +1,526 @@
+use std::{cell::RefCell, ops::{Deref, DerefMut}, rc::{Rc, Weak}};
+
+use crate::{container::HasLength, heaps::heap::{heap_type_match, Heap, HeapType, HeapTypeMatch}};
+
+type Link<T> = Rc<RefCell<FibonacciNode<T>>>;
+type WeakLink<T> = Weak<RefCell<FibonacciNode<T>>>;
+
+pub struct FibonacciHeap<T: PartialOrd + Clone> {
+    h_type: HeapType,
+    length: usize,
+    roots: NodeList<T>,
+    top: Option<Link<T>>,
+    top_value: Option<Rc<T>>,
+    owner: Rc<Owner>,
+}
+
+/// Refers to a value pushed with [`FibonacciHeap::push_with_handle`].
+/// The handle stays valid until the value is popped and follows it through `meld` and
+/// `merge`; any other heap rejects it.
+pub struct FibonacciHandle<T: PartialOrd + Clone> {
+    node: WeakLink<T>,
+}
+
+impl<T> Clone for FibonacciHandle<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl<T> FibonacciHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    pub fn new(heap_type: HeapType) -> Self {
+        Self {
+            h_type: heap_type,
+            length: 0,
+            roots: NodeList::new(),
+            top: None,
+            top_value: None,
+            owner: Rc::new(Owner::default()),
+        }
+    }
+
+    pub fn push_with_handle(&mut self, value: T) -> FibonacciHandle<T> {
+        let node = Rc::new(RefCell::new(FibonacciNode::new(value, self.owner.clone())));
+        let handle = FibonacciHandle {
+            node: Rc::downgrade(&node),
+        };
+        self.roots.push_back(node.clone());
+        self.update_top(&node);
+        self.length += 1;
+        handle
+    }
+
+    /// Moves the value behind `handle` towards the top of the heap.
+    /// Returns `false` if the handle is stale, belongs to another heap, or `value` would
+    /// move it away from the top.
+    pub fn decrease_key(&mut self, handle: &FibonacciHandle<T>, value: T) -> bool {
+        let Some(node) = handle.node.upgrade() else {
+            return false;
+        };
+        if !self.owns(&node) {
+            return false;
+        }
+        if self.h_type.is_correct(&*node.borrow().value, &value) {
+            return false;
+        }
+        node.borrow_mut().value = Rc::new(value);
+
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent
+            && self.h_type.is_correct(&*node.borrow().value, &*parent.borrow().value)
+        {
+            self.cut(&node, &parent);
+            self.cascading_cut(parent);
+        }
+
+        if self.top.as_ref().is_some_and(|top| Rc::ptr_eq(top, &node)) {
+            self.set_top(node);
+        } else {
+            self.update_top(&node);
+        }
+        true
+    }
+
+    fn owns(&self, node: &Link<T>) -> bool {
+        let mut node_ref = node.borrow_mut();
+        node_ref.owner = Owner::current(&node_ref.owner);
+        Rc::ptr_eq(&node_ref.owner, &self.owner)
+    }
+
+    fn set_top(&mut self, node: Link<T>) {
+        self.top_value = Some(node.borrow().value.clone());
+        self.top = Some(node);
+    }
+
+    fn update_top(&mut self, node: &Link<T>) {
+        let is_new_top = match self.top_value.as_ref() {
+            Some(top_value) => self.h_type.is_correct(&*node.borrow().value, &**top_value),
+            None => true,
+        };
+        if is_new_top {
+            self.set_top(node.clone());
+        }
+    }
+
+    fn cut(&mut self, node: &Link<T>, parent: &Link<T>) {
+        let mut parent_ref = parent.borrow_mut();
+        parent_ref.children.remove(node);
+        parent_ref.degree -= 1;
+        drop(parent_ref);
+
+        let mut node_ref = node.borrow_mut();
+        node_ref.parent = Weak::new();
+        node_ref.marked = false;
+        drop(node_ref);
+
+        self.roots.push_back(node.clone());
+    }
+
+    fn cascading_cut(&mut self, mut node: Link<T>) {
+        loop {
+            let parent = node.borrow().parent.upgrade();
+            let Some(parent) = parent else {
+                break;
+            };
+            if !node.borrow().marked {
+                node.borrow_mut().marked = true;
+                break;
+            }
+            self.cut(&node, &parent);
+            node = parent;
+        }
+    }
+
+    // Makes the children of `node` roots of their own.
+    fn promote_children(&mut self, node: &Link<T>) {
+        let mut node_ref = node.borrow_mut();
+        let mut children = std::mem::replace(&mut node_ref.children, NodeList::new());
+        node_ref.degree = 0;
+        drop(node_ref);
+        for child in children.nodes() {
+            let mut child = child.borrow_mut();
+            child.parent = Weak::new();
+            child.marked = false;
+        }
+        self.roots.append(&mut children);
+    }
+
+    // The top keeps its node, so handles to it stay valid.
+    fn replace_top_value(&mut self, value: Rc<T>) {
+        let Some(top) = self.top.take() else {
+            return;
+        };
+        self.top_value = None;
+        top.borrow_mut().value = value;
+        self.promote_children(&top);
+        self.consolidate();
+    }
+
+    fn link(first: Link<T>, second: Link<T>, heap_type: HeapType) -> Link<T> {
+        let (parent, child) = if heap_type.is_correct(&*second.borrow().value, &*first.borrow().value) {
+            (second, first)
+        } else {
+            (first, second)
+        };
+
+        let mut child_ref = child.borrow_mut();
+        child_ref.parent = Rc::downgrade(&parent);
+        child_ref.marked = false;
+        drop(child_ref);
+
+        let mut parent_ref = parent.borrow_mut();
+        parent_ref.children.push_back(child);
+        parent_ref.degree += 1;
+        drop(parent_ref);
+
+        parent
+    }
+
+    fn consolidate(&mut self) {
+        let mut by_degree: Vec<Option<Link<T>>> = vec![];
+        while let Some(mut node) = self.roots.pop_front() {
+            loop {
+                let degree = node.borrow().degree;
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(other) => node = Self::link(node, other, self.h_type),
+                    None => {
+                        by_degree[degree] = Some(node);
+                        break;
+                    }
+                }
+            }
+        }
+
+        for node in by_degree.into_iter().flatten() {
+            self.roots.push_back(node.clone());
+            self.update_top(&node);
+        }
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut pending = self.roots.nodes();
+        while let Some(node) = pending.pop() {
+            let node = node.borrow();
+            result.push((*node.value).clone());
+            pending.extend(node.children.nodes());
+        }
+        result
+    }
+}
+
+impl<T> HasLength for FibonacciHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+impl<T> Clone for FibonacciHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn clone(&self) -> Self {
+        let mut result = Self::new(self.h_type);
+        for value in self.to_vec() {
+            result.push(value);
+        }
+        result
+    }
+}
+
+impl<T> Heap<T> for FibonacciHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    type Order = HeapType;
+    type PeekMut<'a> = PeekMut<'a, T> where Self: 'a;
+
+    fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        let value = self.top_value.clone()?;
+        Some(PeekMut {
+            heap: self,
+            value,
+            changed: false,
+        })
+    }
+
+    // Pushing is constant time here, so building one value at a time is already linear.
+    fn from_vec(elements: Vec<T>, order: HeapType) -> Self {
+        let mut result = Self::new(order);
+        for value in elements {
+            result.push(value);
+        }
+        result
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.top_value.as_deref()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let top = self.top.take()?;
+        self.top_value = None;
+        self.roots.remove(&top);
+        self.promote_children(&top);
+        self.length -= 1;
+        self.consolidate();
+
+        let value = match Rc::try_unwrap(top) {
+            Ok(node) => node.into_inner().value,
+            Err(node) => node.borrow().value.clone(),
+        };
+        Some(Rc::try_unwrap(value).unwrap_or_else(|value| (*value).clone()))
+    }
+
+    fn push(&mut self, value: T) {
+        self.push_with_handle(value);
+    }
+
+    fn meld(&mut self, other: &mut Self) {
+        other.owner.forward_to(&self.owner);
+        other.owner = Rc::new(Owner::default());
+        if self.h_type == other.h_type {
+            self.roots.append(&mut other.roots);
+            self.length += other.length;
+            other.length = 0;
+            other.top_value = None;
+            if let Some(top) = other.top.take() {
+                self.update_top(&top);
+            }
+        } else {
+            // The trees were built under the other order, so every node becomes a root of its
+            // own and `pop` relinks them under this one.
+            let mut pending = vec![std::mem::replace(&mut other.roots, NodeList::new())];
+            while let Some(mut nodes) = pending.pop() {
+                while let Some(node) = nodes.pop_front() {
+                    let mut node_ref = node.borrow_mut();
+                    pending.push(std::mem::replace(&mut node_ref.children, NodeList::new()));
+                    node_ref.parent = Weak::new();
+                    node_ref.degree = 0;
+                    node_ref.marked = false;
+                    drop(node_ref);
+                    self.roots.push_back(node.clone());
+                    self.update_top(&node);
+                }
+            }
+            self.length += other.length;
+            *other = Self::new(other.h_type);
+        }
+    }
+
+    fn merge(mut self, mut other: Self, new_heap_type: HeapType) -> Self {
+        match heap_type_match::<T, _>(&new_heap_type, &self.h_type, &other.h_type) {
+            HeapTypeMatch::All | HeapTypeMatch::First => {
+                self.meld(&mut other);
+                self
+            },
+            HeapTypeMatch::Second => {
+                other.meld(&mut self);
+                other
+            },
+            HeapTypeMatch::None => {
+                let mut heap = Self::new(new_heap_type);
+                heap.meld(&mut other);
+                heap.meld(&mut self);
+                heap
+            },
+        }
+    }
+}
+
+/// Changes the top value in place, so its [`FibonacciHandle`] stays valid.
+pub struct PeekMut<'a, T: PartialOrd + Clone> {
+    heap: &'a mut FibonacciHeap<T>,
+    value: Rc<T>,
+    changed: bool,
+}
+
+impl<T> Deref for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed = true;
+        Rc::make_mut(&mut self.value)
+    }
+}
+
+impl<T> Drop for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    fn drop(&mut self) {
+        if self.changed {
+            self.heap.replace_top_value(self.value.clone());
+        }
+    }
+}
+
+// Identifies the heap a node belongs to. `meld` forwards the token of the emptied heap to
+// the receiving one instead of visiting every node, like a union-find parent link.
+#[derive(Default)]
+struct Owner {
+    forward: RefCell<Option<Rc<Owner>>>,
+}
+
+impl Owner {
+    fn forward_to(&self, owner: &Rc<Owner>) {
+        *self.forward.borrow_mut() = Some(owner.clone());
+    }
+
+    fn current(owner: &Rc<Owner>) -> Rc<Owner> {
+        let mut current = owner.clone();
+        loop {
+            let next = current.forward.borrow().clone();
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        if !Rc::ptr_eq(owner, &current) {
+            owner.forward_to(&current);
+        }
+        current
+    }
+}
+
+struct FibonacciNode<T> {
+    value: Rc<T>,
+    owner: Rc<Owner>,
+    degree: usize,
+    marked: bool,
+    parent: WeakLink<T>,
+    prev: WeakLink<T>,
+    next: Option<Link<T>>,
+    children: NodeList<T>,
+}
+
+impl<T> FibonacciNode<T> {
+    fn new(value: T, owner: Rc<Owner>) -> Self {
+        Self {
+            value: Rc::new(value),
+            owner,
+            degree: 0,
+            marked: false,
+            parent: Weak::new(),
+            prev: Weak::new(),
+            next: None,
+            children: NodeList::new(),
+        }
+    }
+}
+
+struct NodeList<T> {
+    head: Option<Link<T>>,
+    tail: WeakLink<T>,
+}
+
+impl<T> NodeList<T> {
+    fn new() -> Self {
+        Self {
+            head: None,
+            tail: Weak::new(),
+        }
+    }
+
+    fn nodes(&self) -> Vec<Link<T>> {
+        let mut result = vec![];
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            current = node.borrow().next.clone();
+            result.push(node);
+        }
+        result
+    }
+
+    fn push_back(&mut self, node: Link<T>) {
+        let tail = std::mem::replace(&mut self.tail, Rc::downgrade(&node));
+        match tail.upgrade() {
+            Some(tail) => {
+                node.borrow_mut().prev = Rc::downgrade(&tail);
+                tail.borrow_mut().next = Some(node);
+            },
+            None => {
+                node.borrow_mut().prev = Weak::new();
+                self.head = Some(node);
+            },
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        let Some(other_head) = other.head.take() else {
+            return;
+        };
+        let other_tail = std::mem::take(&mut other.tail);
+        match self.tail.upgrade() {
+            Some(tail) => {
+                other_head.borrow_mut().prev = Rc::downgrade(&tail);
+                tail.borrow_mut().next = Some(other_head);
+            },
+            None => self.head = Some(other_head),
+        }
+        self.tail = other_tail;
+    }
+
+    fn remove(&mut self, node: &Link<T>) {
+        let mut node_ref = node.borrow_mut();
+        let prev = std::mem::take(&mut node_ref.prev).upgrade();
+        let next = node_ref.next.take();
+        drop(node_ref);
+
+        let prev_link = prev.as_ref().map(Rc::downgrade).unwrap_or_default();
+        match next.as_ref() {
+            Some(next) => next.borrow_mut().prev = prev_link,
+            None => self.tail = prev_link,
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.head = next,
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<Link<T>> {
+        let head = self.head.clone()?;
+        self.remove(&head);
+        Some(head)
+    }
+}
+
+impl<T> Drop for NodeList<T> {
+    fn drop(&mut self) {
+        // Unlinks iteratively so that long sibling chains and deep trees do not overflow the stack.
+        let mut pending = vec![self.head.take()];
+        while let Some(mut current) = pending.pop() {
+            while let Some(node) = current {
+                let mut node_ref = node.borrow_mut();
+                current = node_ref.next.take();
+                pending.push(node_ref.children.head.take());
+            }
+        }
+    }
+}
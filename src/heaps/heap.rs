@@ -1,5 +1,12 @@
-#[derive(Clone, Copy, PartialEq)]
+use std::{cmp::Ordering, ops::DerefMut, sync::atomic::{self, AtomicUsize}};
+
+use crate::container::HasLength;
+
+/// `Min` is the default, so collected heaps pop their smallest value first.
+#[derive(Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeapType {
+    #[default]
     Min,
     Max,
 }
@@ -17,10 +24,326 @@ impl HeapType {
     }
 }
 
-pub trait Heap<T: PartialOrd + Clone> {
+/// Decides which of two values belongs closer to the top of a heap.
+pub trait HeapOrder<T> {
+    fn is_correct(&self, parent: &T, child: &T) -> bool;
+
+    /// Lets heaps skip reordering when melding. Two closures of one type can still order values
+    /// differently, so orders are only trusted to match when they say so.
+    fn is_same_order(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl<T: PartialOrd> HeapOrder<T> for HeapType {
+    fn is_correct(&self, parent: &T, child: &T) -> bool {
+        HeapType::is_correct(self, parent, child)
+    }
+
+    fn is_same_order(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Min;
+
+impl<T: PartialOrd> HeapOrder<T> for Min {
+    fn is_correct(&self, parent: &T, child: &T) -> bool {
+        child > parent
+    }
+
+    fn is_same_order(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Max;
+
+impl<T: PartialOrd> HeapOrder<T> for Max {
+    fn is_correct(&self, parent: &T, child: &T) -> bool {
+        child < parent
+    }
+
+    fn is_same_order(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Keeps the value with the smallest key on top.
+#[derive(Clone, Copy)]
+pub struct ByKey<F>(pub F);
+
+impl<T, K, F> HeapOrder<T> for ByKey<F>
+where
+    K: PartialOrd,
+    F: Fn(&T) -> K,
+{
+    fn is_correct(&self, parent: &T, child: &T) -> bool {
+        (self.0)(child) > (self.0)(parent)
+    }
+}
+
+/// Keeps the value that compares as [`Ordering::Less`] on top, e.g. `By(f64::total_cmp)`.
+#[derive(Clone, Copy)]
+pub struct By<F>(pub F);
+
+impl<T, F> HeapOrder<T> for By<F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn is_correct(&self, parent: &T, child: &T) -> bool {
+        (self.0)(child, parent) == Ordering::Greater
+    }
+}
+
+// Heaps are stored as their order and values; the structure is rebuilt on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename = "Heap")]
+pub(crate) struct SerializedHeapRef<'a, T, O> {
+    pub(crate) order: &'a O,
+    pub(crate) elements: Vec<&'a T>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Heap")]
+pub(crate) struct SerializedHeap<T, O> {
+    pub(crate) order: O,
+    pub(crate) elements: Vec<T>,
+}
+
+pub(crate) enum HeapTypeMatch {
+    All,
+    First,
+    Second,
+    None,
+}
+
+pub(crate) fn heap_type_match<T, O: HeapOrder<T>>(
+    main_type: &O,
+    first_type: &O,
+    second_type: &O,
+) -> HeapTypeMatch {
+    let first_match = main_type.is_same_order(first_type);
+    let second_match = main_type.is_same_order(second_type);
+    if first_match & second_match {
+        HeapTypeMatch::All
+    } else if first_match {
+        HeapTypeMatch::First
+    } else if second_match {
+        HeapTypeMatch::Second
+    } else {
+        HeapTypeMatch::None
+    }
+}
+
+pub trait Heap<T: Clone> {
+    type Order;
+    /// Gives mutable access to the top value and restores the heap when dropped.
+    type PeekMut<'a>: DerefMut<Target = T> where Self: 'a;
+
+    /// Builds a heap holding `elements`, in linear time where the structure allows it.
+    fn from_vec(elements: Vec<T>, order: Self::Order) -> Self;
     fn peek(&self) -> Option<&T>;
+    fn peek_mut(&mut self) -> Option<Self::PeekMut<'_>>;
     fn pop(&mut self) -> Option<T>;
     fn push(&mut self, value: T);
     fn meld(&mut self, other: &mut Self);
-    fn merge(self, other: Self, new_heap_type: HeapType) -> Self;
+    fn merge(self, other: Self, new_heap_type: Self::Order) -> Self;
+
+    /// Same as `push` followed by `pop`.
+    fn push_pop(&mut self, value: T) -> T {
+        self.push(value);
+        self.pop().unwrap()
+    }
+
+    /// Same as `pop` followed by `push`.
+    fn replace(&mut self, value: T) -> Option<T> {
+        let result = self.pop();
+        self.push(value);
+        result
+    }
+
+    /// Pushes many values at once, which some heaps do faster than one `push` at a time.
+    fn extend_from<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+/// Pops every value of a heap in order.
+pub struct IntoSortedIter<T, H> {
+    heap: H,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T, H> IntoSortedIter<T, H> {
+    pub(crate) fn new(heap: H) -> Self {
+        Self {
+            heap,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, H> Iterator for IntoSortedIter<T, H>
+where
+    T: Clone,
+    H: Heap<T> + HasLength,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, H> ExactSizeIterator for IntoSortedIter<T, H>
+where
+    T: Clone,
+    H: Heap<T> + HasLength,
+{}
+
+/// Identifies a value pushed into an [`AddressableHeap`].
+/// A handle only works on the heap that returned it and on its clones. `meld` and `merge` keep
+/// the handles of the heap they are called on, while values moved in from the other heap get
+/// new ones.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HeapHandle {
+    owner: usize,
+    slot: usize,
+    generation: usize,
+}
+
+/// Follows values as a heap moves them around. Heaps built with [`NoHandles`] skip the
+/// bookkeeping entirely; [`Handles`] makes them addressable.
+pub trait HandleStore: Clone + Default {
+    /// Starts tracking a value stored after the last position.
+    fn push(&mut self);
+    fn swap(&mut self, first: usize, second: usize);
+    /// Stops tracking the value at the last position.
+    fn pop(&mut self);
+    fn clear(&mut self);
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct NoHandles;
+
+impl HandleStore for NoHandles {
+    fn push(&mut self) {}
+
+    fn swap(&mut self, _first: usize, _second: usize) {}
+
+    fn pop(&mut self) {}
+
+    fn clear(&mut self) {}
+}
+
+/// Keeps the position of every value in a table indexed by the handle's slot.
+/// Freed slots are reused under a new generation, so stale handles find nothing.
+#[derive(Clone)]
+pub struct Handles {
+    owner: usize,
+    slot_at: Vec<usize>,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+#[derive(Clone)]
+struct Slot {
+    position: Option<usize>,
+    generation: usize,
+}
+
+impl Default for Handles {
+    fn default() -> Self {
+        static NEXT_OWNER: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            owner: NEXT_OWNER.fetch_add(1, atomic::Ordering::Relaxed),
+            slot_at: vec![],
+            slots: vec![],
+            free: vec![],
+        }
+    }
+}
+
+impl Handles {
+    pub(crate) fn push_handle(&mut self) -> HeapHandle {
+        let position = Some(self.slot_at.len());
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].position = position;
+                slot
+            },
+            None => {
+                self.slots.push(Slot { position, generation: 0 });
+                self.slots.len() - 1
+            },
+        };
+        self.slot_at.push(slot);
+        HeapHandle {
+            owner: self.owner,
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    pub(crate) fn position(&self, handle: HeapHandle) -> Option<usize> {
+        if handle.owner != self.owner {
+            return None;
+        }
+        self.slots.get(handle.slot)
+            .filter(|slot| slot.generation == handle.generation)?
+            .position
+    }
+
+    fn release(&mut self, slot: usize) {
+        let slot_ref = &mut self.slots[slot];
+        slot_ref.position = None;
+        slot_ref.generation = slot_ref.generation.wrapping_add(1);
+        self.free.push(slot);
+    }
+}
+
+impl HandleStore for Handles {
+    fn push(&mut self) {
+        self.push_handle();
+    }
+
+    fn swap(&mut self, first: usize, second: usize) {
+        self.slot_at.swap(first, second);
+        self.slots[self.slot_at[first]].position = Some(first);
+        self.slots[self.slot_at[second]].position = Some(second);
+    }
+
+    fn pop(&mut self) {
+        if let Some(slot) = self.slot_at.pop() {
+            self.release(slot);
+        }
+    }
+
+    fn clear(&mut self) {
+        while let Some(slot) = self.slot_at.pop() {
+            self.release(slot);
+        }
+    }
+}
+
+pub trait AddressableHeap<T: Clone>: Heap<T> {
+    fn push_with_handle(&mut self, value: T) -> HeapHandle;
+    fn get(&self, handle: HeapHandle) -> Option<&T>;
+    /// Replaces the value behind `handle`, moving it up or down as needed.
+    /// Returns `false` if the handle does not belong to this heap.
+    fn update(&mut self, handle: HeapHandle, value: T) -> bool;
+    fn remove(&mut self, handle: HeapHandle) -> Option<T>;
 }
\ No newline at end of file
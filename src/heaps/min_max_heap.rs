@@ -0,0 +1,208 @@
+use crate::container::HasLength;
+
+/// An implicit heap whose even levels are ordered like a min-heap and odd levels like a
+/// max-heap, so the smallest value is the root and the largest is one of its children.
+#[derive(Clone)]
+pub struct MinMaxHeap<T: PartialOrd> {
+    elements: Vec<T>,
+}
+
+impl<T> MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    pub fn new() -> Self {
+        Self {
+            elements: vec![],
+        }
+    }
+
+    pub fn from_vec(elements: Vec<T>) -> Self {
+        let mut result = Self { elements };
+        for index in (0..(result.len() >> 1)).rev() {
+            result.trickle_down(index);
+        }
+        result
+    }
+
+    /// Visits the values in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.elements.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.elements[index])
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+        Some(self.remove_at(index))
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.elements.push(value);
+        self.bubble_up(self.len() - 1);
+    }
+
+    fn is_min_level(index: usize) -> bool {
+        (index + 1).ilog2() & 1 == 0
+    }
+
+    // Whether `first` belongs above `second` on a min level, or on a max level when `min` is unset.
+    fn is_above(first: &T, second: &T, min: bool) -> bool {
+        if min { first < second } else { first > second }
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.elements[2] > self.elements[1] { 2 } else { 1 }),
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        let result = self.elements.swap_remove(index);
+        if index < self.len() {
+            self.trickle_down(index);
+        }
+        result
+    }
+
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+        let parent = (index - 1) >> 1;
+        let min = Self::is_min_level(index);
+        if Self::is_above(&self.elements[parent], &self.elements[index], !min) {
+            self.bubble_up_level(index, min);
+        } else {
+            self.elements.swap(index, parent);
+            self.bubble_up_level(parent, !min);
+        }
+    }
+
+    // Moves the value up through grandparents, which share its kind of level.
+    fn bubble_up_level(&mut self, mut index: usize, min: bool) {
+        while index > 2 {
+            let grandparent = (index - 3) >> 2;
+            if !Self::is_above(&self.elements[index], &self.elements[grandparent], min) {
+                return;
+            }
+            self.elements.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        let min = Self::is_min_level(index);
+        loop {
+            let first_child = (index << 1) + 1;
+            let first_grandchild = (first_child << 1) + 1;
+            let descendants = (first_child..(first_child + 2))
+                .chain(first_grandchild..(first_grandchild + 4))
+                .take_while(|&descendant| descendant < self.len());
+            let Some(best) = descendants.reduce(|best, descendant| {
+                if Self::is_above(&self.elements[descendant], &self.elements[best], min) { descendant } else { best }
+            }) else {
+                return;
+            };
+            if !Self::is_above(&self.elements[best], &self.elements[index], min) {
+                return;
+            }
+            self.elements.swap(best, index);
+            if best < first_grandchild {
+                return;
+            }
+            let parent = (best - 1) >> 1;
+            if Self::is_above(&self.elements[best], &self.elements[parent], !min) {
+                self.elements.swap(best, parent);
+            }
+            index = best;
+        }
+    }
+}
+
+impl<T> Default for MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HasLength for MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+}
+
+impl<T> FromIterator<T> for MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T> Extend<T> for MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for MinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for MinMaxHeap<T>
+where
+    T: PartialOrd + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.elements.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for MinMaxHeap<T>
+where
+    T: PartialOrd + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from_vec)
+    }
+}
\ No newline at end of file
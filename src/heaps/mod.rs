@@ -1,3 +1,7 @@
 pub mod heap;
 pub mod binary_heap;
-pub mod binomial_heap;
\ No newline at end of file
+pub mod binomial_heap;
+pub mod dary_heap;
+pub mod fibonacci_heap;
+pub mod min_max_heap;
+pub mod pairing_heap;
\ No newline at end of file
@@ -0,0 +1,245 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{container::HasLength, heaps::heap::{heap_type_match, Heap, HeapType, HeapTypeMatch}};
+
+pub struct PairingHeap<T: PartialOrd + Clone> {
+    h_type: HeapType,
+    length: usize,
+    root: Option<Box<PairingNode<T>>>,
+}
+
+impl<T> PairingHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    pub fn new(heap_type: HeapType) -> Self {
+        Self {
+            h_type: heap_type,
+            length: 0,
+            root: None,
+        }
+    }
+
+    fn link(
+        mut first: Box<PairingNode<T>>,
+        mut second: Box<PairingNode<T>>,
+        heap_type: HeapType,
+    ) -> Box<PairingNode<T>> {
+        if heap_type.is_correct(&second.value, &first.value) {
+            second.children.push(first);
+            second
+        } else {
+            first.children.push(second);
+            first
+        }
+    }
+
+    fn merge_pairs(mut nodes: Vec<Box<PairingNode<T>>>, heap_type: HeapType) -> Option<Box<PairingNode<T>>> {
+        let mut pairs = Vec::with_capacity(nodes.len().div_ceil(2));
+        let mut nodes = nodes.drain(..);
+        while let Some(first) = nodes.next() {
+            match nodes.next() {
+                Some(second) => pairs.push(Self::link(first, second, heap_type)),
+                None => pairs.push(first),
+            }
+        }
+
+        let mut result = pairs.pop()?;
+        while let Some(node) = pairs.pop() {
+            result = Self::link(node, result, heap_type);
+        }
+        Some(result)
+    }
+
+    fn push_node(&mut self, node: Box<PairingNode<T>>) {
+        self.root = Some(match self.root.take() {
+            Some(root) => Self::link(root, node, self.h_type),
+            None => node,
+        });
+    }
+
+    // The root may now belong below some of its children, so it is relinked as a single node.
+    fn fix_root(&mut self) {
+        let Some(mut root) = self.root.take() else {
+            return;
+        };
+        if root.children.iter().all(|child| !self.h_type.is_correct(&child.value, &root.value)) {
+            self.root = Some(root);
+            return;
+        }
+        let children = std::mem::take(&mut root.children);
+        self.root = Self::merge_pairs(children, self.h_type);
+        self.push_node(root);
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut pending: Vec<&PairingNode<T>> = self.root.as_deref().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            result.push(node.value.clone());
+            pending.extend(node.children.iter().map(|child| child.as_ref()));
+        }
+        result
+    }
+}
+
+impl<T> HasLength for PairingHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+impl<T> Clone for PairingHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn clone(&self) -> Self {
+        let mut result = Self::new(self.h_type);
+        for value in self.to_vec() {
+            result.push(value);
+        }
+        result
+    }
+}
+
+impl<T> Drop for PairingHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn drop(&mut self) {
+        // Sorted insertions produce long chains, so the nodes are freed without recursion.
+        let mut pending: Vec<Box<PairingNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.children);
+        }
+    }
+}
+
+impl<T> Heap<T> for PairingHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    type Order = HeapType;
+    type PeekMut<'a> = PeekMut<'a, T> where Self: 'a;
+
+    fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        if self.root.is_some() {
+            Some(PeekMut { heap: self })
+        } else {
+            None
+        }
+    }
+
+    // Pushing is constant time here, so building one value at a time is already linear.
+    fn from_vec(elements: Vec<T>, order: HeapType) -> Self {
+        let mut result = Self::new(order);
+        for value in elements {
+            result.push(value);
+        }
+        result
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let root = *self.root.take()?;
+        self.root = Self::merge_pairs(root.children, self.h_type);
+        self.length -= 1;
+        Some(root.value)
+    }
+
+    fn push(&mut self, value: T) {
+        self.push_node(Box::new(PairingNode::from(value)));
+        self.length += 1;
+    }
+
+    fn meld(&mut self, other: &mut Self) {
+        if self.h_type == other.h_type {
+            if let Some(root) = other.root.take() {
+                self.push_node(root);
+            }
+            self.length += other.length;
+            other.length = 0;
+        } else {
+            for value in other.to_vec() {
+                self.push(value);
+            }
+            *other = Self::new(other.h_type);
+        }
+    }
+
+    fn merge(mut self, mut other: Self, new_heap_type: HeapType) -> Self {
+        match heap_type_match::<T, _>(&new_heap_type, &self.h_type, &other.h_type) {
+            HeapTypeMatch::All | HeapTypeMatch::First => {
+                self.meld(&mut other);
+                self
+            },
+            HeapTypeMatch::Second => {
+                other.meld(&mut self);
+                other
+            },
+            HeapTypeMatch::None => {
+                let mut heap = Self::new(new_heap_type);
+                for value in other.to_vec() {
+                    heap.push(value);
+                }
+                for value in self.to_vec() {
+                    heap.push(value);
+                }
+                heap
+            },
+        }
+    }
+}
+
+pub struct PeekMut<'a, T: PartialOrd + Clone> {
+    heap: &'a mut PairingHeap<T>,
+}
+
+impl<T> Deref for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.root.as_ref().unwrap().value
+    }
+}
+
+impl<T> DerefMut for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.root.as_mut().unwrap().value
+    }
+}
+
+impl<T> Drop for PeekMut<'_, T>
+where
+    T: PartialOrd + Clone,
+{
+    fn drop(&mut self) {
+        self.heap.fix_root();
+    }
+}
+
+struct PairingNode<T> {
+    value: T,
+    children: Vec<Box<PairingNode<T>>>,
+}
+
+impl<T> From<T> for PairingNode<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            children: vec![],
+        }
+    }
+}
@@ -2,9 +2,95 @@ pub mod heaps;
 pub mod container;
 pub mod probalistic;
 
+#[cfg(test)]
+mod bin_heap {
+    use crate::{heaps::binary_heap::BinaryHeap, heaps::heap::{AddressableHeap,By,Heap,HeapType,Max}};
+
+    #[test]
+    fn pop() {
+        let mut heap = BinaryHeap::build_min(vec![7, 3, 9, 1, 4]);
+        heap.push(2);
+        heap.push(8);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.pop(), Some(8));
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn handles() {
+        let mut heap = BinaryHeap::addressable(HeapType::Max);
+        let first = heap.push_with_handle(5);
+        let second = heap.push_with_handle(3);
+        let third = heap.push_with_handle(8);
+
+        assert!(heap.update(second, 10));
+        assert_eq!(heap.get(second), Some(&10));
+        assert_eq!(heap.peek(), Some(&10));
+        assert_eq!(heap.remove(third), Some(8));
+        assert_eq!(heap.get(third), None);
+        assert!(!heap.update(third, 1));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.get(first), Some(&5));
+    }
+
+    #[test]
+    fn custom_order() {
+        let mut heap = BinaryHeap::build_with_order(vec![2.5, f64::NAN, -1.0, 7.0], By(f64::total_cmp));
+        heap.push(0.5);
+        assert_eq!(heap.pop(), Some(-1.0));
+        assert_eq!(heap.pop(), Some(0.5));
+        assert_eq!(heap.pop(), Some(2.5));
+        assert_eq!(heap.pop(), Some(7.0));
+        assert!(heap.pop().unwrap().is_nan());
+
+        let mut heap = BinaryHeap::with_order(Max);
+        heap.push(3);
+        heap.push(9);
+        assert_eq!(heap.merge(BinaryHeap::build_with_order(vec![4], Max), Max).peek(), Some(&9));
+    }
+
+    #[test]
+    fn iterators() {
+        let mut heap: BinaryHeap<i32, Max> = vec![4, 1, 7].into_iter().collect();
+        heap.extend([9, 3]);
+        let mut values: Vec<_> = heap.iter().copied().collect();
+        values.sort();
+        assert_eq!(values, vec![1, 3, 4, 7, 9]);
+        assert_eq!(heap.clone().into_sorted_vec(), vec![9, 7, 4, 3, 1]);
+        assert_eq!(heap.clone().into_iter().count(), 5);
+
+        assert_eq!(heap.drain().count(), 5);
+        assert_eq!(heap.pop(), None);
+        heap.push(2);
+        assert_eq!(heap.into_sorted_iter().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn top_k_operations() {
+        let mut heap = BinaryHeap::addressable(HeapType::Min);
+        assert_eq!(heap.push_pop(5), 5);
+        assert_eq!(heap.replace(5), None);
+        heap.extend_from(0..100);
+        assert_eq!(heap.push_pop(-1), -1);
+        assert_eq!(heap.push_pop(50), 0);
+        assert_eq!(heap.replace(-2), Some(1));
+
+        let handle = heap.push_with_handle(-3);
+        *heap.peek_mut().unwrap() = 200;
+        assert_eq!(heap.get(handle), Some(&200));
+        assert_eq!(heap.peek(), Some(&-2));
+        assert_eq!(heap.into_sorted_vec().len(), 102);
+    }
+}
+
 #[cfg(test)]
 mod binom_heap {
-    use crate::{heaps::binomial_heap::BinomialHeap, heaps::heap::{Heap,HeapType}};
+    use crate::{container::HasLength, heaps::binomial_heap::BinomialHeap, heaps::heap::{AddressableHeap,ByKey,Heap,HeapType}};
 
     #[test]
     fn pop() {
@@ -62,23 +148,1004 @@ mod binom_heap {
         assert_eq!(heap2.peek(), Some(&1));
         assert_eq!(heap3.peek(), Some(&11));
     }
+
+    #[test]
+    fn handles() {
+        let mut heap1 = BinomialHeap::addressable(HeapType::Min);
+        let mut heap2 = BinomialHeap::addressable(HeapType::Min);
+        let handles: Vec<_> = (0..6).map(|value| heap1.push_with_handle(value * 2)).collect();
+        let moved = heap2.push_with_handle(7);
+        heap1.meld(&mut heap2);
+
+        assert!(heap1.update(handles[4], 1));
+        assert!(heap1.update(handles[0], 9));
+        assert_eq!(heap1.get(moved), None);
+        assert_eq!(heap2.get(moved), None);
+        assert_eq!(heap1.get(handles[4]), Some(&1));
+        assert_eq!(heap1.pop(), Some(1));
+        assert_eq!(heap1.pop(), Some(2));
+        assert_eq!(heap1.remove(handles[3]), Some(6));
+        assert_eq!(heap1.pop(), Some(4));
+        assert_eq!(heap1.pop(), Some(7));
+        assert_eq!(heap1.pop(), Some(9));
+        assert_eq!(heap1.pop(), Some(10));
+        assert_eq!(heap1.pop(), None);
+
+        let mut heap3 = BinomialHeap::addressable(HeapType::Max);
+        let handle = heap3.push_with_handle(5);
+        let kept = heap1.push_with_handle(3);
+        heap1.meld(&mut heap3);
+        assert!(heap3.is_empty());
+        assert_eq!(heap3.get(handle), None);
+        assert_eq!(heap1.len(), 2);
+        assert_eq!(heap1.remove(kept), Some(3));
+        assert_eq!(heap1.get(kept), None);
+    }
+
+    #[test]
+    fn custom_order() {
+        #[derive(Clone)]
+        struct Task {
+            name: &'static str,
+            priority: u32,
+        }
+
+        let mut heap1 = BinomialHeap::with_order(ByKey(|task: &Task| task.priority));
+        let mut heap2 = heap1.clone();
+        heap1.push(Task { name: "write", priority: 3 });
+        heap1.push(Task { name: "build", priority: 1 });
+        heap2.push(Task { name: "test", priority: 2 });
+        heap1.meld(&mut heap2);
+
+        assert_eq!(heap1.pop().map(|task| task.name), Some("build"));
+        assert_eq!(heap1.pop().map(|task| task.name), Some("test"));
+        assert_eq!(heap1.pop().map(|task| task.name), Some("write"));
+    }
+
+    #[test]
+    fn order_without_clone() {
+        let key: Box<dyn Fn(&i32) -> i32> = Box::new(|value| -value);
+        let mut heap = BinomialHeap::with_order(ByKey(key));
+        heap.extend_from([3, 9, 1]);
+        assert_eq!(heap.pop(), Some(9));
+        assert_eq!(heap.into_sorted_vec(), vec![3, 1]);
+    }
+
+    #[test]
+    fn closure_orders_with_different_captures() {
+        let by_sign = |sign: i32| ByKey(move |value: &i32| sign * value);
+        let mut ascending = BinomialHeap::with_order(by_sign(1));
+        let mut descending = BinomialHeap::with_order(by_sign(-1));
+        ascending.extend_from([5, 1, 3]);
+        descending.extend_from([2, 6, 4]);
+        ascending.meld(&mut descending);
+
+        assert_eq!(ascending.into_sorted_vec(), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut heap: BinomialHeap<i32> = (0..10).rev().collect();
+        heap.extend([20, -5]);
+        let sorted = vec![-5, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 20];
+        assert_eq!(heap.iter().len(), 12);
+        assert_eq!((&heap).into_iter().sum::<i32>(), 60);
+        assert_eq!(heap.clone().into_sorted_vec(), sorted);
+
+        let mut values: Vec<_> = heap.drain().collect();
+        values.sort();
+        assert_eq!(values, sorted);
+        assert_eq!(heap.peek(), None);
+        heap.push(3);
+        assert_eq!(heap.into_iter().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn top_k_operations() {
+        let mut heap = BinomialHeap::addressable(HeapType::Max);
+        heap.extend_from([4, 8, 1, 9, 6, 3, 7]);
+        assert_eq!(heap.push_pop(2), 9);
+        assert_eq!(heap.replace(5), Some(8));
+
+        let handle = heap.push_with_handle(10);
+        *heap.peek_mut().unwrap() = 0;
+        assert_eq!(heap.get(handle), Some(&0));
+        assert_eq!(heap.into_sorted_vec(), vec![7, 6, 5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn from_vec() {
+        let values: Vec<u64> = (0..1000).map(|value| (value * 7919) % 1009).collect();
+        let mut heap = BinomialHeap::<u64>::from_vec(values.clone(), HeapType::Max);
+        assert_eq!(heap.len(), 1000);
+        heap.extend_from(values.iter().map(|value| value + 2000));
+
+        let mut expected: Vec<_> = values.iter().flat_map(|&value| [value, value + 2000]).collect();
+        expected.sort_by(|first, second| second.cmp(first));
+        assert_eq!(heap.into_sorted_vec(), expected);
+        assert_eq!(BinomialHeap::<u8>::from_vec(vec![], HeapType::Min).peek(), None);
+    }
+}
+
+#[cfg(test)]
+mod fib_heap {
+    use crate::{container::HasLength, heaps::fibonacci_heap::FibonacciHeap, heaps::heap::{Heap,HeapType}};
+
+    #[test]
+    fn pop() {
+        let mut heap = FibonacciHeap::new(HeapType::Min);
+        for value in [7, 2, 9, 5, 1, 8, 3] {
+            heap.push(value);
+        }
+        for expected in [1, 2, 3, 5, 7, 8, 9] {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut heap = FibonacciHeap::new(HeapType::Max);
+        let handles: Vec<_> = (0..10).map(|value| heap.push_with_handle(value)).collect();
+        assert_eq!(heap.pop(), Some(9));
+
+        assert!(heap.decrease_key(&handles[3], 20));
+        assert!(!heap.decrease_key(&handles[5], 1));
+        assert!(!heap.decrease_key(&handles[9], 30));
+        assert_eq!(heap.peek(), Some(&20));
+        assert_eq!(heap.pop(), Some(20));
+        assert!(heap.decrease_key(&handles[0], 15));
+        assert_eq!(heap.pop(), Some(15));
+        assert_eq!(heap.pop(), Some(8));
+    }
+
+    #[test]
+    fn merge() {
+        let mut heap1 = FibonacciHeap::new(HeapType::Min);
+        let mut heap2 = FibonacciHeap::new(HeapType::Min);
+        heap1.push(2);
+        heap1.push(5);
+        heap2.push(1);
+        heap2.push(11);
+
+        let heap3 = heap1.clone().merge(heap2.clone(), HeapType::Max);
+        heap1.meld(&mut heap2);
+
+        assert_eq!(heap1.peek(), Some(&1));
+        assert_eq!(heap2.peek(), None);
+        assert_eq!(heap3.peek(), Some(&11));
+    }
+
+    #[test]
+    fn handles_across_orders() {
+        let mut heap1 = FibonacciHeap::new(HeapType::Min);
+        let mut heap2 = FibonacciHeap::new(HeapType::Max);
+        let handles: Vec<_> = (0..8).map(|value| heap2.push_with_handle(value * 10)).collect();
+        assert_eq!(heap2.pop(), Some(70));
+        heap1.push(15);
+        heap1.meld(&mut heap2);
+
+        assert!(heap1.decrease_key(&handles[6], 5));
+        assert!(heap1.decrease_key(&handles[2], 1));
+        assert_eq!(heap1.pop(), Some(0));
+        assert_eq!(heap1.pop(), Some(1));
+        assert_eq!(heap1.pop(), Some(5));
+        assert_eq!(heap1.pop(), Some(10));
+        assert_eq!(heap1.pop(), Some(15));
+
+        let heap3 = heap1.merge(FibonacciHeap::new(HeapType::Max), HeapType::Max);
+        let mut heap3 = heap3.merge(FibonacciHeap::new(HeapType::Min), HeapType::Min);
+        assert!(heap3.decrease_key(&handles[5], 2));
+        assert_eq!(heap3.pop(), Some(2));
+        assert_eq!(heap3.pop(), Some(30));
+        assert_eq!(heap3.len(), 1);
+    }
+
+    #[test]
+    fn foreign_handles() {
+        let mut heap1 = FibonacciHeap::new(HeapType::Min);
+        let mut heap2 = FibonacciHeap::new(HeapType::Min);
+        let foreign = heap1.push_with_handle(5);
+        heap2.push(3);
+        assert!(!heap2.decrease_key(&foreign, 1));
+        assert_eq!(heap2.pop(), Some(3));
+        assert_eq!(heap2.pop(), None);
+
+        let moved = heap2.push_with_handle(8);
+        heap1.meld(&mut heap2);
+        assert!(!heap2.decrease_key(&moved, 1));
+        assert_eq!(heap2.pop(), None);
+
+        let mut heap3 = FibonacciHeap::new(HeapType::Max);
+        heap3.meld(&mut heap1);
+        assert!(!heap1.decrease_key(&moved, 1));
+        assert!(heap3.decrease_key(&moved, 9));
+        assert!(heap3.decrease_key(&foreign, 7));
+        assert_eq!(heap3.pop(), Some(9));
+        assert_eq!(heap3.pop(), Some(7));
+        assert_eq!(heap3.pop(), None);
+    }
+
+    #[test]
+    fn peek_mut_keeps_handles() {
+        let mut heap = FibonacciHeap::new(HeapType::Min);
+        let handles: Vec<_> = (0..8).map(|value| heap.push_with_handle(value)).collect();
+        assert_eq!(heap.pop(), Some(0));
+
+        *heap.peek_mut().unwrap() = 20;
+        assert_eq!(heap.peek(), Some(&2));
+        assert!(heap.decrease_key(&handles[1], 0));
+        assert_eq!(heap.pop(), Some(0));
+
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(*top, 2);
+        drop(top);
+        assert!(heap.decrease_key(&handles[2], -1));
+        assert_eq!(std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>(), vec![-1, 3, 4, 5, 6, 7]);
+    }
+}
+
+#[cfg(test)]
+mod pair_heap {
+    use crate::{container::HasLength, heaps::pairing_heap::PairingHeap, heaps::heap::{Heap,HeapType}};
+
+    #[test]
+    fn pop() {
+        let mut heap = PairingHeap::new(HeapType::Max);
+        for value in [4, 9, 1, 7, 3, 8] {
+            heap.push(value);
+        }
+        for expected in [9, 8, 7, 4, 3, 1] {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn merge() {
+        let mut heap1 = PairingHeap::new(HeapType::Min);
+        let mut heap2 = PairingHeap::new(HeapType::Min);
+        heap1.push(2);
+        heap1.push(5);
+        heap1.push(4);
+
+        heap2.push(1);
+        heap2.push(3);
+        heap2.push(11);
+
+        let heap3 = heap1.clone().merge(heap2.clone(), HeapType::Max);
+        heap1.meld(&mut heap2);
+
+        assert_eq!(heap1.peek(), Some(&1));
+        assert_eq!(heap2.peek(), None);
+        assert_eq!(heap3.peek(), Some(&11));
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut heap = PairingHeap::new(HeapType::Min);
+        for value in (0..200_000).rev() {
+            heap.push(value);
+        }
+        assert_eq!(heap.pop(), Some(0));
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut heap = PairingHeap::new(HeapType::Min);
+        for value in [4, 2, 6] {
+            heap.push(value);
+        }
+        *heap.peek_mut().unwrap() += 10;
+        assert_eq!(heap.replace(1), Some(4));
+        assert_eq!(heap.push_pop(3), 1);
+        for expected in [3, 6, 12] {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn forgotten_peek_mut() {
+        let mut heap = PairingHeap::new(HeapType::Max);
+        heap.extend_from([5, 8, 2]);
+        std::mem::forget(heap.peek_mut());
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.pop(), Some(8));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.pop(), Some(2));
+    }
+}
+
+#[cfg(test)]
+mod dary_heap {
+    use crate::{heaps::dary_heap::DaryHeap, heaps::heap::{Heap,HeapType,Max}};
+
+    #[test]
+    fn pop() {
+        let values: Vec<u32> = (0..1000).map(|value| (value * 7919) % 1009).collect();
+        let mut heap = DaryHeap::<u32, 4>::new(HeapType::Min);
+        for &value in &values[..500] {
+            heap.push(value);
+        }
+        heap.extend_from(values[500..].iter().copied());
+
+        let mut expected = values;
+        expected.sort();
+        for value in expected {
+            assert_eq!(heap.pop(), Some(value));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn top_k_operations() {
+        let mut heap: DaryHeap<i32, 3, Max> = (0..20).collect();
+        assert_eq!(heap.push_pop(25), 25);
+        assert_eq!(heap.replace(-1), Some(19));
+        *heap.peek_mut().unwrap() = 7;
+        assert_eq!(heap.peek(), Some(&17));
+
+        let other = DaryHeap::from_vec(vec![30, 40], Max);
+        let heap = heap.merge(other, Max);
+        assert_eq!(heap.into_sorted_iter().take(4).collect::<Vec<_>>(), vec![40, 30, 17, 16]);
+    }
+}
+
+#[cfg(test)]
+mod min_max_heap {
+    use crate::{container::HasLength, heaps::min_max_heap::MinMaxHeap};
+
+    #[test]
+    fn both_ends() {
+        let values: Vec<u32> = (0..1000).map(|value| (value * 7919) % 1009).collect();
+        let mut heap: MinMaxHeap<u32> = values[..500].iter().copied().collect();
+        heap.extend(values[500..].iter().copied());
+
+        let mut expected = values;
+        expected.sort();
+        let mut expected = expected.into_iter();
+        while let Some(min) = expected.next() {
+            assert_eq!(heap.peek_min(), Some(&min));
+            assert_eq!(heap.pop_min(), Some(min));
+            if let Some(max) = expected.next_back() {
+                assert_eq!(heap.peek_max(), Some(&max));
+                assert_eq!(heap.pop_max(), Some(max));
+            }
+        }
+        assert_eq!(heap.pop_max(), None);
+    }
+
+    #[test]
+    fn bounded_top_k() {
+        let mut best = MinMaxHeap::new();
+        for value in [5, 1, 9, 3, 7, 8, 2, 6] {
+            best.push(value);
+            if best.len() > 3 {
+                best.pop_min();
+            }
+        }
+        assert_eq!(best.peek_max(), Some(&9));
+        assert_eq!(best.peek_min(), Some(&7));
+    }
 }
 
 #[cfg(test)]
 mod b_filter {
-    use super::probalistic::bloom_filter::BloomFilter;
+    use super::probalistic::{bloom_filter::{BitBloomFilter, BloomFilter, BloomFilterView, HasherWithSeed, Indexing, InsertOutcome, MurmurHash}, error::{DecodeError, InsertError, MergeError}, key::{Bytes, Hashed}};
+    use crate::container::HasLength;
 
     #[test]
     fn insert_and_contains() {
         let mut bf = BloomFilter::with_capacity(5);
-        bf.insert(10u8);
-        bf.insert(12);
-        bf.insert(13);
-        bf.insert(14);
-        bf.insert(15);
-        bf.insert(16);
+        assert_eq!(bf.insert(10u8), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(12), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(13), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(14), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(15), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(16), InsertOutcome::CapacityReached);
         assert!(bf.contains(&10));
         assert!(!bf.contains(&7));
         assert!(!bf.contains(&16));
     }
+
+    #[test]
+    fn seeds_do_not_collide() {
+        // Without spreading, seed 1 on value 0 hashed like seed 0 on value 1,
+        // so small values shared all their positions.
+        assert_ne!(MurmurHash.hash(&0u32.to_le_bytes(), 1), MurmurHash.hash(&1u32.to_le_bytes(), 0));
+        let mut bf = BloomFilter::with_error_rate(1000, 0.01);
+        assert_eq!(bf.insert(0u32), InsertOutcome::Inserted);
+        assert!(!bf.contains(&1));
+        assert!(!bf.contains(&2));
+    }
+
+    #[test]
+    fn insert_outcome() {
+        let mut bf = BloomFilter::with_capacity(2);
+        assert_eq!(bf.insert(1u32), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(1), InsertOutcome::ProbablyPresent);
+        assert_eq!(bf.try_insert(2), Ok(true));
+        assert_eq!(bf.try_insert(2), Ok(false));
+        assert_eq!(bf.insert(3), InsertOutcome::CapacityReached);
+        assert_eq!(bf.try_insert(4), Err(InsertError::Full));
+        assert!(!bf.contains(&3));
+    }
+
+    #[test]
+    fn any_key() {
+        let mut bf = BitBloomFilter::with_all(100, 0.01, MurmurHash);
+        assert_eq!(bf.insert(Bytes(String::from("alpha"))), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(Bytes(String::from("beta"))), InsertOutcome::Inserted);
+        assert!(bf.contains("alpha"));
+        assert!(!bf.contains("gamma"));
+
+        let mut bf = BloomFilter::with_capacity(10);
+        assert_eq!(bf.insert(Bytes(b"key".to_vec())), InsertOutcome::Inserted);
+        assert!(bf.contains(&b"key"[..]));
+        assert!(bf.contains(&Bytes(b"key".to_vec())));
+        assert!(!bf.contains(&b"other"[..]));
+
+        let mut bf = BloomFilter::with_capacity(10);
+        assert_eq!(bf.insert(Hashed(("user", 42u64))), InsertOutcome::Inserted);
+        assert!(bf.contains(&Hashed(("user", 42))));
+        assert!(!bf.contains(&Hashed(("user", 43))));
+    }
+
+    #[test]
+    fn bits_only() {
+        let mut bf = BitBloomFilter::with_all(1000, 0.01, MurmurHash);
+        for value in 0..1000u32 {
+            let _ = bf.insert(value);
+        }
+        assert!((0..1000u32).all(|value| bf.contains(&value)));
+        let false_positives = (1000..11000u32).filter(|value| bf.contains(value)).count();
+        assert!(false_positives < 200);
+    }
+
+    #[test]
+    fn indexing_keeps_error_rate() {
+        for indexing in [Indexing::Seeded, Indexing::Double, Indexing::EnhancedDouble] {
+            let mut bf = BitBloomFilter::with_all(10_000, 0.01, MurmurHash).with_indexing(indexing);
+            for value in 0..10_000u64 {
+                let _ = bf.insert(value);
+            }
+            assert!((0..10_000u64).all(|value| bf.contains(&value)));
+            let false_positives = (10_000..110_000u64).filter(|value| bf.contains(value)).count();
+            assert!(false_positives < 1300, "{indexing:?}: {false_positives}");
+
+            let loaded = BitBloomFilter::<u64>::from_bytes(&bf.to_bytes(), MurmurHash).unwrap();
+            assert_eq!(loaded.indexing(), indexing);
+        }
+
+        let seeded = BitBloomFilter::<u64>::with_all(100, 0.01, MurmurHash);
+        let mut double = BitBloomFilter::with_all(100, 0.01, MurmurHash).with_indexing(Indexing::Double);
+        assert_eq!(double.union(&seeded), Err(MergeError::IndexingMismatch));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bf = BitBloomFilter::with_all(100, 0.01, MurmurHash);
+        for value in 0..100u64 {
+            let _ = bf.insert(value);
+        }
+
+        let bytes = bf.to_bytes();
+        let loaded = BitBloomFilter::<u64>::from_bytes(&bytes, MurmurHash).unwrap();
+        assert_eq!(loaded.len(), bf.len());
+        assert!((0..100u64).all(|value| loaded.contains(&value)));
+        assert_eq!(loaded.to_bytes(), bytes);
+
+        let streamed = BitBloomFilter::<u64>::read_from(bytes.as_slice(), MurmurHash).unwrap();
+        assert_eq!(streamed.to_bytes(), bytes);
+
+        let mut corrupted = bytes.clone();
+        corrupted[50] ^= 1;
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&corrupted, MurmurHash), Err(DecodeError::ChecksumMismatch)));
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&bytes[..60], MurmurHash), Err(DecodeError::Truncated)));
+        assert!(matches!(BitBloomFilter::<u64>::read_from(&bytes[..60], MurmurHash), Err(DecodeError::Truncated)));
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&bytes[1..], MurmurHash), Err(DecodeError::BadMagic)));
+    }
+
+    #[test]
+    fn format_version() {
+        let seeded = BitBloomFilter::<u64>::with_all(100, 0.01, MurmurHash).to_bytes();
+        assert_eq!(seeded[4..6], [1, 0]);
+
+        let mut double = BitBloomFilter::<u64>::with_all(100, 0.01, MurmurHash).with_indexing(Indexing::Double).to_bytes();
+        assert_eq!(double[4..6], [2, 0]);
+        double[4] = 1;
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&double, MurmurHash), Err(DecodeError::Corrupted)));
+        double[4] = 3;
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&double, MurmurHash), Err(DecodeError::UnsupportedVersion(3))));
+    }
+
+    #[test]
+    fn forged_mask_length() {
+        let mut bytes = BitBloomFilter::<u64>::with_all(100, 0.01, MurmurHash).to_bytes();
+        bytes[32..40].copy_from_slice(&(u64::MAX >> 1).to_le_bytes());
+        assert!(matches!(BitBloomFilter::<u64>::read_from(bytes.as_slice(), MurmurHash), Err(DecodeError::Truncated)));
+        assert!(matches!(BitBloomFilter::<u64>::from_bytes(&bytes, MurmurHash), Err(DecodeError::Truncated)));
+    }
+
+    #[test]
+    fn borrowed_view() {
+        let mut bf = BitBloomFilter::with_all(100, 0.01, MurmurHash).with_indexing(Indexing::Double);
+        for value in 0..100u64 {
+            let _ = bf.insert(value);
+        }
+        let bytes = bf.to_bytes();
+
+        let view = BloomFilterView::<u64>::new(&bytes, MurmurHash).unwrap();
+        assert_eq!(view.len(), bf.len());
+        assert_eq!(view.indexing(), Indexing::Double);
+        assert!((0..1000u64).all(|value| view.contains(&value) == bf.contains(&value)));
+
+        let mut corrupted = bytes.clone();
+        corrupted[50] ^= 1;
+        assert!(matches!(BloomFilterView::<u64>::new(&corrupted, MurmurHash), Err(DecodeError::ChecksumMismatch)));
+        assert!(BloomFilterView::<u64>::new_unverified(&corrupted, MurmurHash).is_ok());
+        assert!(matches!(BloomFilterView::<u64>::new(&bytes[..60], MurmurHash), Err(DecodeError::Truncated)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut bf1 = BitBloomFilter::with_all(2000, 0.01, MurmurHash);
+        let mut bf2 = BitBloomFilter::with_all(2000, 0.01, MurmurHash);
+        for value in 0..1000u32 {
+            let _ = bf1.insert(value);
+        }
+        for value in 500..1500u32 {
+            let _ = bf2.insert(value);
+        }
+
+        let intersection = bf1.estimate_intersection_size(&bf2).unwrap();
+        assert!((intersection - 500.0).abs() < 50.0);
+        assert!((bf1.jaccard(&bf2).unwrap() - 1.0 / 3.0).abs() < 0.05);
+
+        let mut union = BitBloomFilter::with_all(2000, 0.01, MurmurHash);
+        union.union(&bf1).unwrap();
+        union.union(&bf2).unwrap();
+        assert!((0..1500u32).all(|value| union.contains(&value)));
+        assert!((union.len() as f64 - 1500.0).abs() < 50.0);
+
+        bf1.intersect(&bf2).unwrap();
+        assert!((500..1000u32).all(|value| bf1.contains(&value)));
+        assert!((bf1.len() as f64 - 500.0).abs() < 50.0);
+
+        let other = BitBloomFilter::with_all(100, 0.01, MurmurHash);
+        assert_eq!(bf1.union(&other), Err(MergeError::SizeMismatch));
+    }
+
+    #[test]
+    fn stored_set_operations() {
+        let mut bf1 = BloomFilter::with_error_rate(200, 0.01);
+        let mut bf2 = BloomFilter::with_error_rate(200, 0.01);
+        for value in 0..100u32 {
+            let _ = bf1.insert(value);
+        }
+        for value in 50..150u32 {
+            let _ = bf2.insert(value);
+        }
+
+        let mut union = BloomFilter::with_error_rate(200, 0.01);
+        union.union(&bf1).unwrap();
+        union.union(&bf2).unwrap();
+        assert_eq!(union.len(), 150);
+        assert!((0..150u32).all(|value| union.contains(&value)));
+
+        bf1.intersect(&bf2).unwrap();
+        assert_eq!(bf1.len(), 50);
+        assert!((0..150u32).all(|value| bf1.contains(&value) == (50..100).contains(&value)));
+
+        let other = BloomFilter::with_error_rate(20, 0.01);
+        assert_eq!(bf1.union(&other), Err(MergeError::SizeMismatch));
+    }
+}
+
+#[cfg(test)]
+mod sb_filter {
+    use super::probalistic::{bloom_filter::{InsertOutcome, MurmurHash, NoStore}, scalable_bloom_filter::ScalableBloomFilter};
+    use crate::container::HasLength;
+
+    #[test]
+    fn grows_past_capacity() {
+        let mut bf = ScalableBloomFilter::with_error_rate(10, 0.01);
+        for value in 0..1000u32 {
+            assert_eq!(bf.insert(value), InsertOutcome::Inserted);
+        }
+        assert_eq!(bf.insert(7), InsertOutcome::ProbablyPresent);
+        assert_eq!(bf.len(), 1000);
+        assert!(bf.layer_count() > 1);
+        assert!((0..1000u32).all(|value| bf.contains(&value)));
+    }
+
+    #[test]
+    fn bits_only_error_rate() {
+        let mut bf = ScalableBloomFilter::<u32, MurmurHash, NoStore<u32>>::with_all(100, 0.01, MurmurHash);
+        for value in 0..10_000u32 {
+            let _ = bf.insert(value);
+        }
+        assert!((0..10_000u32).all(|value| bf.contains(&value)));
+        let false_positives = (10_000..110_000u32).filter(|value| bf.contains(value)).count();
+        assert!(false_positives < 1_500);
+    }
+}
+
+#[cfg(test)]
+mod ab_filter {
+    use super::probalistic::{atomic_bloom_filter::AtomicBloomFilter, bloom_filter::{Indexing, InsertOutcome}};
+    use crate::container::HasLength;
+    use std::sync::Arc;
+
+    #[test]
+    fn shared_between_threads() {
+        let bf = Arc::new(AtomicBloomFilter::with_error_rate(40_000, 0.01).with_indexing(Indexing::EnhancedDouble));
+        let workers: Vec<_> = (0..4u64)
+            .map(|worker| {
+                let bf = Arc::clone(&bf);
+                std::thread::spawn(move || {
+                    for value in (worker * 10_000)..((worker + 1) * 10_000) {
+                        let _ = bf.insert(&value);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert!(bf.len() <= 40_000);
+        assert_eq!(bf.insert(&7), InsertOutcome::ProbablyPresent);
+        assert!((0..40_000u64).all(|value| bf.contains(&value)));
+        let false_positives = (40_000..140_000u64).filter(|value| bf.contains(value)).count();
+        assert!(false_positives < 1_300);
+    }
+}
+
+#[cfg(test)]
+mod bb_filter {
+    use super::probalistic::{blocked_bloom_filter::BlockedBloomFilter, bloom_filter::InsertOutcome, error::InsertError};
+    use crate::container::HasLength;
+
+    #[test]
+    fn insert_and_contains() {
+        let mut bf = BlockedBloomFilter::with_error_rate(10_000, 0.01);
+        for value in 0..10_000u64 {
+            let _ = bf.insert(&value);
+        }
+        assert_eq!(bf.insert(&42), InsertOutcome::ProbablyPresent);
+        assert!(bf.len() <= 10_000);
+        assert!((0..10_000u64).all(|value| bf.contains(&value)));
+        let false_positives = (10_000..110_000u64).filter(|value| bf.contains(value)).count();
+        assert!(false_positives < 2_000, "{false_positives}");
+    }
+
+    #[test]
+    fn capacity() {
+        let mut bf = BlockedBloomFilter::with_capacity(2);
+        assert_eq!(bf.insert(&1u32), InsertOutcome::Inserted);
+        assert_eq!(bf.try_insert(&2), Ok(true));
+        assert_eq!(bf.try_insert(&2), Ok(false));
+        assert_eq!(bf.insert(&3), InsertOutcome::CapacityReached);
+        assert_eq!(bf.try_insert(&3), Err(InsertError::Full));
+        assert_eq!(bf.len(), 2);
+    }
+}
+
+#[cfg(test)]
+mod hashers {
+    use super::probalistic::{bloom_filter::{BitBloomFilter, HasherWithSeed}, hashers::{BuildHasherAdapter, FnvHash}};
+    use std::collections::hash_map::RandomState;
+
+    fn false_positives<H: HasherWithSeed>(hasher: H) -> usize {
+        let mut bf = BitBloomFilter::with_all(10_000, 0.01, hasher);
+        for value in 0..10_000u64 {
+            let _ = bf.insert(value);
+        }
+        assert!((0..10_000u64).all(|value| bf.contains(&value)));
+        (10_000..110_000u64).filter(|value| bf.contains(value)).count()
+    }
+
+    #[test]
+    fn keep_error_rate() {
+        assert!(false_positives(FnvHash) < 1_300);
+        assert!(false_positives(BuildHasherAdapter(RandomState::new())) < 1_300);
+        #[cfg(feature = "xxhash")]
+        {
+            use super::probalistic::hashers::{XxHash64, Xxh3};
+            assert!(false_positives(XxHash64) < 1_300);
+            assert!(false_positives(Xxh3) < 1_300);
+        }
+        #[cfg(feature = "siphash")]
+        assert!(false_positives(super::probalistic::hashers::SipHash::random()) < 1_300);
+    }
+
+    #[test]
+    #[cfg(feature = "siphash")]
+    fn siphash_id_covers_key() {
+        use super::probalistic::{error::DecodeError, hashers::SipHash};
+        assert_eq!(SipHash::with_keys(1, 2).id(), SipHash::with_keys(1, 2).id());
+        assert_ne!(SipHash::with_keys(1, 2).id(), SipHash::with_keys(1, 3).id());
+
+        let mut bf = BitBloomFilter::with_all(100, 0.01, SipHash::with_keys(1, 2));
+        let _ = bf.insert(7u64);
+        let bytes = bf.to_bytes();
+        assert!(BitBloomFilter::<u64, _>::from_bytes(&bytes, SipHash::with_keys(1, 2)).is_ok());
+        assert!(matches!(BitBloomFilter::<u64, _>::from_bytes(&bytes, SipHash::with_keys(1, 3)), Err(DecodeError::HasherMismatch)));
+    }
+
+    #[test]
+    #[cfg(feature = "xxhash")]
+    fn xxhash_reference_values() {
+        use super::probalistic::hashers::{XxHash64, Xxh3};
+        assert_eq!(XxHash64.hash(b"", 0) as u64, 0xEF46_DB37_51D8_E999);
+        assert_eq!(Xxh3.hash(b"", 0) as u64, 0x2D06_8005_38D3_94C2);
+    }
+}
+
+#[cfg(test)]
+mod cb_filter {
+    use super::probalistic::counting_bloom_filter::CountingBloomFilter;
+    use crate::container::HasLength;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut bf = CountingBloomFilter::with_error_rate(100, 0.01);
+        for value in 0..50u32 {
+            bf.insert(&value);
+        }
+        bf.insert(&7);
+        assert_eq!(bf.len(), 51);
+        assert!((0..50u32).all(|value| bf.contains(&value)));
+
+        assert!(bf.remove(&7));
+        assert!(bf.contains(&7));
+        assert!(bf.remove(&7));
+        for value in 10..50u32 {
+            assert!(bf.remove(&value));
+        }
+        assert!(!bf.contains(&7));
+        assert!(!bf.remove(&7));
+        assert_eq!(bf.len(), 9);
+        assert!((0..7u32).all(|value| bf.contains(&value)));
+    }
+
+    #[test]
+    fn remove_more_than_inserted() {
+        let mut bf = CountingBloomFilter::with_capacity(10);
+        for _ in 0..20 {
+            bf.insert(&1u32);
+        }
+        for _ in 0..20 {
+            assert!(bf.remove(&1));
+        }
+        assert!(!bf.remove(&1));
+        assert_eq!(bf.len(), 0);
+    }
+
+    #[test]
+    fn str_keys() {
+        let mut bf: CountingBloomFilter<str> = CountingBloomFilter::with_capacity(100);
+        bf.insert("alpha");
+        bf.insert(&String::from("beta"));
+        assert!(bf.contains("alpha"));
+        assert!(!bf.contains("gamma"));
+        assert!(bf.remove("beta"));
+        assert_eq!(bf.len(), 1);
+    }
+}
+
+// Stands in for a keyed hasher, whose id changes with the key.
+#[cfg(test)]
+mod test_hasher {
+    use super::probalistic::bloom_filter::{HasherWithSeed, MurmurHash};
+
+    #[derive(Clone)]
+    pub(crate) struct Keyed(pub(crate) u32);
+
+    impl HasherWithSeed for Keyed {
+        fn hash(&self, data: &[u8], seed: usize) -> usize {
+            MurmurHash.hash(data, seed ^ self.0 as usize)
+        }
+
+        fn id(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod cm_sketch {
+    use super::{probalistic::{count_min::CountMinSketch, bloom_filter::MurmurHash, error::MergeError}, test_hasher::Keyed};
+
+    #[test]
+    fn add_and_estimate() {
+        let mut sketch = CountMinSketch::with_error(0.001, 0.01);
+        for value in 0..1000u32 {
+            sketch.add(&value, 1);
+        }
+        sketch.add(&42, 500);
+        assert!(sketch.estimate(&42) >= 501);
+        assert!(sketch.estimate(&42) <= 505);
+        assert!(sketch.estimate(&7) >= 1);
+    }
+
+    #[test]
+    fn merge() {
+        let mut sketch1 = CountMinSketch::with_error(0.01, 0.01);
+        let mut sketch2 = CountMinSketch::with_error(0.01, 0.01);
+        sketch1.add(&1u64, 3);
+        sketch2.add(&1u64, 4);
+        assert_eq!(sketch1.merge(&sketch2), Ok(()));
+        assert_eq!(sketch1.estimate(&1), 7);
+
+        let other = CountMinSketch::with_size(10, 2, MurmurHash);
+        assert_eq!(sketch1.merge(&other), Err(MergeError::SizeMismatch));
+    }
+
+    #[test]
+    fn merge_with_other_hasher() {
+        let mut sketch1 = CountMinSketch::<u64, _>::with_size(100, 4, Keyed(1));
+        let sketch2 = CountMinSketch::with_size(100, 4, Keyed(2));
+        assert_eq!(sketch1.merge(&sketch2), Err(MergeError::HasherMismatch));
+        assert_eq!(sketch1.merge(&CountMinSketch::with_size(100, 4, Keyed(1))), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "epsilon must be positive")]
+    fn zero_epsilon() {
+        CountMinSketch::<u64>::with_error(0.0, 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "delta must be in (0, 1)")]
+    fn delta_out_of_range() {
+        CountMinSketch::<u64>::with_error(0.01, 1.5);
+    }
+
+    #[test]
+    fn slice_keys() {
+        let mut sketch: CountMinSketch<[u8]> = CountMinSketch::with_error(0.01, 0.01);
+        sketch.add(b"key", 3);
+        sketch.add(String::from("key").as_bytes(), 2);
+        assert!(sketch.estimate(b"key") >= 5);
+    }
+}
+
+#[cfg(test)]
+mod hll {
+    use super::{probalistic::{hyperloglog::HyperLogLog, error::MergeError}, test_hasher::Keyed};
+
+    #[test]
+    fn estimate() {
+        let mut hll = HyperLogLog::with_precision(14);
+        assert_eq!(hll.estimate(), 0.0);
+        for value in 0..100_000u64 {
+            hll.insert(&value);
+            hll.insert(&value);
+        }
+        assert!((hll.estimate() - 100_000.0).abs() < 3_000.0);
+    }
+
+    #[test]
+    fn merge() {
+        let mut hll1 = HyperLogLog::with_precision(12);
+        let mut hll2 = HyperLogLog::with_precision(12);
+        for value in 0..600u32 {
+            hll1.insert(&value);
+        }
+        for value in 400..1000u32 {
+            hll2.insert(&value);
+        }
+        assert_eq!(hll1.merge(&hll2), Ok(()));
+        assert!((hll1.estimate() - 1000.0).abs() < 50.0);
+        assert_eq!(hll1.merge(&HyperLogLog::with_precision(10)), Err(MergeError::SizeMismatch));
+    }
+
+    #[test]
+    fn merge_with_other_hasher() {
+        let mut hll1 = HyperLogLog::<u32, _>::with_hasher(10, Keyed(1));
+        hll1.insert(&1);
+        assert_eq!(hll1.merge(&HyperLogLog::with_hasher(10, Keyed(2))), Err(MergeError::HasherMismatch));
+        assert_eq!(hll1.merge(&HyperLogLog::with_hasher(10, Keyed(1))), Ok(()));
+    }
+}
+
+#[cfg(test)]
+mod c_filter {
+    use super::probalistic::{cuckoo_filter::CuckooFilter, error::InsertError};
+    use crate::container::HasLength;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut cf = CuckooFilter::with_capacity(1000);
+        for value in 0..900u32 {
+            assert_eq!(cf.insert(&value), Ok(()));
+        }
+        assert_eq!(cf.len(), 900);
+        assert!((0..900u32).all(|value| cf.contains(&value)));
+        assert!((900..10_900u32).filter(|value| cf.contains(value)).count() < 10);
+
+        for value in 0..450u32 {
+            assert!(cf.remove(&value));
+        }
+        assert_eq!(cf.len(), 450);
+        assert!((450..900u32).all(|value| cf.contains(&value)));
+    }
+
+    #[test]
+    fn full() {
+        let mut cf = CuckooFilter::with_capacity(8);
+        let mut inserted = vec![];
+        for value in 0..100u32 {
+            match cf.insert(&value) {
+                Ok(()) => inserted.push(value),
+                Err(error) => {
+                    assert_eq!(error, InsertError::Full);
+                    break;
+                }
+            }
+        }
+        assert_eq!(cf.len(), inserted.len());
+        assert!(inserted.iter().all(|value| cf.contains(value)));
+    }
+
+    #[test]
+    fn str_keys() {
+        let mut cf: CuckooFilter<str> = CuckooFilter::with_capacity(100);
+        assert_eq!(cf.insert("alpha"), Ok(()));
+        assert!(cf.contains("alpha"));
+        assert!(!cf.contains("beta"));
+        assert!(cf.remove("alpha"));
+        assert!(!cf.contains("alpha"));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_support {
+    use crate::{heaps::{binary_heap::BinaryHeap, binomial_heap::BinomialHeap, dary_heap::DaryHeap, heap::{Heap, HeapType}}, probalistic::bloom_filter::{BitBloomFilter, BloomFilter, InsertOutcome, MurmurHash}};
+
+    #[test]
+    fn heaps() {
+        let heap = BinaryHeap::build_max(vec![3, 8, 1, 5]);
+        let json = serde_json::to_string(&heap).unwrap();
+        let mut loaded: BinaryHeap<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.pop(), Some(8));
+        assert_eq!(loaded.pop(), Some(5));
+
+        let mut heap = BinomialHeap::new(HeapType::Min);
+        for value in [4, 2, 9, 7, 1] {
+            heap.push(value);
+        }
+        let json = serde_json::to_string(&heap).unwrap();
+        let mut loaded: BinomialHeap<i32> = serde_json::from_str(&json).unwrap();
+        for expected in [1, 2, 4, 7, 9] {
+            assert_eq!(loaded.pop(), Some(expected));
+        }
+
+        let heap = DaryHeap::<i32, 4>::from_vec(vec![6, 2, 8], HeapType::Max);
+        let json = serde_json::to_string(&heap).unwrap();
+        let loaded: DaryHeap<i32, 4> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.into_sorted_vec(), vec![8, 6, 2]);
+    }
+
+    #[test]
+    fn bloom_filters() {
+        let mut bf = BloomFilter::with_capacity(10);
+        assert_eq!(bf.insert(3u32), InsertOutcome::Inserted);
+        assert_eq!(bf.insert(4), InsertOutcome::Inserted);
+        let json = serde_json::to_string(&bf).unwrap();
+        let loaded: BloomFilter<u32> = serde_json::from_str(&json).unwrap();
+        assert!(loaded.contains(&3) && loaded.contains(&4));
+        assert!(!loaded.contains(&5));
+
+        let mut bf = BitBloomFilter::with_all(100, 0.01, MurmurHash);
+        for value in 0..100u64 {
+            let _ = bf.insert(value);
+        }
+        let json = serde_json::to_string(&bf).unwrap();
+        let loaded: BitBloomFilter<u64> = serde_json::from_str(&json).unwrap();
+        assert!((0..100u64).all(|value| loaded.contains(&value)));
+        assert_eq!(loaded.to_bytes(), bf.to_bytes());
+    }
 }
\ No newline at end of file
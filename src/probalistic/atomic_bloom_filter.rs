@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::{container::HasLength, probalistic::{bloom_filter::{fast_mask_and_hasher_size, mask_and_hasher_size, HasherWithSeed, Indexes, Indexing, InsertOutcome, MurmurHash, ABS_ERROR_RATE_LN}, key::BloomKey}};
+
+/// A bits-only Bloom filter that can be shared between threads, e.g. through an `Arc`.
+/// Bits are only ever set, so a value is reported as present once its `insert` has returned.
+pub struct AtomicBloomFilter<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    words: Vec<AtomicU64>,
+    hasher: H,
+    hasher_number: usize,
+    indexing: Indexing,
+    length: AtomicUsize,
+    // No value is stored, so sharing the filter does not depend on `T`.
+    _marker: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<T> AtomicBloomFilter<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_hasher(capacity, MurmurHash)
+    }
+
+    pub fn with_error_rate(capacity: usize, error_rate: f32) -> Self {
+        Self::with_all(capacity, error_rate, MurmurHash)
+    }
+}
+
+impl<T, H> AtomicBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    pub fn with_hasher(capacity: usize, hasher: H) -> Self {
+        let (size_mask, hasher_number) = fast_mask_and_hasher_size(capacity, ABS_ERROR_RATE_LN);
+        Self::with_sizes(size_mask, hasher_number, hasher)
+    }
+
+    pub fn with_all(capacity: usize, error_rate: f32, hasher: H) -> Self {
+        let (size_mask, hasher_number) = mask_and_hasher_size(capacity, error_rate);
+        Self::with_sizes(size_mask, hasher_number, hasher)
+    }
+
+    fn with_sizes(size_mask: usize, hasher_number: usize, hasher: H) -> Self {
+        Self {
+            words: (0..=(size_mask >> 6)).map(|_| AtomicU64::new(0)).collect(),
+            hasher,
+            hasher_number,
+            indexing: Indexing::Seeded,
+            length: AtomicUsize::new(0),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Must be called before anything is inserted, since it changes which bits a value sets.
+    pub fn with_indexing(mut self, indexing: Indexing) -> Self {
+        assert!(self.is_empty(), "indexing of a non-empty filter cannot change");
+        self.indexing = indexing;
+        self
+    }
+
+    fn indexes<'a>(&'a self, bytes: &'a [u8]) -> Indexes<'a, H> {
+        Indexes::new(&self.hasher, bytes, self.indexing, self.hasher_number, self.words.len() << 6)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        value.with_key_bytes(|bytes| {
+            self.indexes(bytes).all(|index| {
+                (self.words[index >> 6].load(Ordering::Relaxed) >> (index & 63)) & 1 == 1
+            })
+        })
+    }
+
+    /// Never reports [`InsertOutcome::CapacityReached`]. When several threads insert the same
+    /// value at once, more than one of them may get [`InsertOutcome::Inserted`].
+    pub fn insert(&self, value: &T) -> InsertOutcome {
+        let changed = value.with_key_bytes(|bytes| {
+            let mut changed = false;
+            for index in self.indexes(bytes) {
+                let bit = 1 << (index & 63);
+                changed |= self.words[index >> 6].fetch_or(bit, Ordering::Relaxed) & bit == 0;
+            }
+            changed
+        });
+        if changed {
+            self.length.fetch_add(1, Ordering::Relaxed);
+            InsertOutcome::Inserted
+        } else {
+            InsertOutcome::ProbablyPresent
+        }
+    }
+}
+
+impl<T, H> HasLength for AtomicBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    /// Counts the insertions that set at least one new bit.
+    fn len(&self) -> usize {
+        self.length.load(Ordering::Relaxed)
+    }
+}
\ No newline at end of file
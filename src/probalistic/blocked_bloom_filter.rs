@@ -0,0 +1,132 @@
+use crate::{container::HasLength, probalistic::{bloom_filter::{fast_mask_and_hasher_size, mask_and_hasher_size, HasherWithSeed, InsertOutcome, MurmurHash, ABS_ERROR_RATE_LN}, error::InsertError, key::BloomKey}};
+
+/// A bits-only Bloom filter that puts all bits of a value into one cache line, so every lookup
+/// reads a single block. The uneven load across blocks makes false positives slightly more
+/// frequent than in [`BloomFilter`](super::bloom_filter::BloomFilter) of the same size.
+pub struct BlockedBloomFilter<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    blocks: Vec<Block>,
+    hasher: H,
+    hasher_number: usize,
+    capacity: usize,
+    length: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> BlockedBloomFilter<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_hasher(capacity, MurmurHash)
+    }
+
+    pub fn with_error_rate(capacity: usize, error_rate: f32) -> Self {
+        Self::with_all(capacity, error_rate, MurmurHash)
+    }
+}
+
+impl<T, H> BlockedBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    pub fn with_hasher(capacity: usize, hasher: H) -> Self {
+        let (size_mask, hasher_number) = fast_mask_and_hasher_size(capacity, ABS_ERROR_RATE_LN);
+        Self::with_sizes(capacity, size_mask, hasher_number, hasher)
+    }
+
+    pub fn with_all(capacity: usize, error_rate: f32, hasher: H) -> Self {
+        let (size_mask, hasher_number) = mask_and_hasher_size(capacity, error_rate);
+        Self::with_sizes(capacity, size_mask, hasher_number, hasher)
+    }
+
+    fn with_sizes(capacity: usize, size_mask: usize, hasher_number: usize, hasher: H) -> Self {
+        Self {
+            blocks: vec![Block::default(); (size_mask / Block::BITS) + 1],
+            hasher,
+            hasher_number,
+            capacity,
+            length: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // The first hash picks the block, the other two drive double hashing inside it.
+    // An odd step visits every bit of the block before repeating.
+    fn locate(&self, bytes: &[u8]) -> (usize, impl Iterator<Item = usize> + use<T, H>) {
+        let block = self.hasher.hash(bytes, 0) % self.blocks.len();
+        let start = self.hasher.hash(bytes, 1);
+        let step = self.hasher.hash(bytes, 2) | 1;
+        let bits = (0..self.hasher_number)
+            .map(move |k| start.wrapping_add(k.wrapping_mul(step)) % Block::BITS);
+        (block, bits)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        value.with_key_bytes(|bytes| {
+            let (block, mut bits) = self.locate(bytes);
+            let block = &self.blocks[block];
+            bits.all(|bit| block.check(bit))
+        })
+    }
+
+    pub fn insert(&mut self, value: &T) -> InsertOutcome {
+        if self.contains(value) {
+            return InsertOutcome::ProbablyPresent;
+        }
+        if !self.is_empty() && (self.len() >= self.capacity) {
+            return InsertOutcome::CapacityReached;
+        }
+        value.with_key_bytes(|bytes| {
+            let (block, bits) = self.locate(bytes);
+            let block = &mut self.blocks[block];
+            for bit in bits {
+                block.update(bit);
+            }
+        });
+        self.length += 1;
+        InsertOutcome::Inserted
+    }
+
+    /// Returns `Ok(false)` if the value is probably present already.
+    pub fn try_insert(&mut self, value: &T) -> Result<bool, InsertError> {
+        match self.insert(value) {
+            InsertOutcome::Inserted => Ok(true),
+            InsertOutcome::ProbablyPresent => Ok(false),
+            InsertOutcome::CapacityReached => Err(InsertError::Full),
+        }
+    }
+}
+
+impl<T, H> HasLength for BlockedBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+#[derive(Clone, Default)]
+#[repr(align(64))]
+struct Block([u64; 8]);
+
+impl Block {
+    const BITS: usize = 512;
+
+    fn check(&self, bit: usize) -> bool {
+        (self.0[bit >> 6] >> (bit & 63)) & 1 == 1
+    }
+
+    fn update(&mut self, bit: usize) {
+        self.0[bit >> 6] |= 1 << (bit & 63);
+    }
+}
\ No newline at end of file
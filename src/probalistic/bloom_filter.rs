@@ -1,27 +1,33 @@
-use std::f32::consts::LN_2;
+use std::{borrow::Borrow, f32::consts::LN_2};
 use murmur2::{murmur2, murmur64a};
-use zerocopy::{Immutable, IntoBytes};
 
-use crate::container::{Container, HasLength, Insertable};
+use crate::{container::{Container, HasLength, Insertable}, probalistic::{error::InsertError, key::BloomKey}};
+
+mod format;
+mod set_ops;
+mod view;
+
+pub use view::BloomFilterView;
 
 pub struct BloomFilter<T, H: HasherWithSeed = MurmurHash, C: Container<T> = Vec<T>>
 where
-    T: Immutable + IntoBytes + PartialEq
+    T: BloomKey + PartialEq
 {
     capacity: usize,
     mask: Mask,
     hasher: H,
     container: C,
     hasher_number: usize,
+    indexing: Indexing,
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T> BloomFilter<T, MurmurHash, Vec<T>>
 where
-    T: Immutable + IntoBytes + PartialEq,
+    T: BloomKey + PartialEq,
 {
     pub fn with_capacity(capacity: usize) -> Self {
-        let (size_mask, hasher_number) = Self::fast_mask_and_hasher_size(capacity, Self::ABS_ERROR_RATE_LN);
+        let (size_mask, hasher_number) = fast_mask_and_hasher_size(capacity, ABS_ERROR_RATE_LN);
         
         Self { 
             capacity,
@@ -30,11 +36,12 @@ where
             container: Vec::new(), 
             _marker: std::marker::PhantomData, 
             hasher_number,
+            indexing: Indexing::Seeded,
         }
     }
 
     pub fn with_error_rate(capacity: usize, error_rate: f32) -> Self {
-        let (size_mask, hasher_number) = Self::mask_and_hasher_size(capacity, error_rate);
+        let (size_mask, hasher_number) = mask_and_hasher_size(capacity, error_rate);
 
         Self {
             capacity,
@@ -42,6 +49,7 @@ where
             hasher: MurmurHash,
             container: Vec::new(),
             hasher_number,
+            indexing: Indexing::Seeded,
             _marker: std::marker::PhantomData,
         }
     }
@@ -49,25 +57,12 @@ where
 
 impl<T, H, C> BloomFilter<T, H, C>
 where
-    T: Immutable + IntoBytes + PartialEq,
+    T: BloomKey + PartialEq,
     H: HasherWithSeed,
     C: Container<T>
 {
-    const ABS_ERROR_RATE_LN: f32 = 2.9957323; // |ln(0.05)|
-
-    fn fast_mask_and_hasher_size(capacity: usize, error_rate: f32) -> (usize, usize) {
-        let size_mask = (error_rate * (capacity as f32)) / (LN_2 * LN_2);
-        let k: usize = (error_rate / LN_2) as usize;
-
-        (size_mask as usize, if k > 0 { k } else { 1 })
-    }
-
-    fn mask_and_hasher_size(capacity: usize, error_rate: f32) -> (usize, usize) {
-        Self::fast_mask_and_hasher_size(capacity, error_rate.ln().abs())
-    }
-
     pub fn with_hasher(capacity: usize, hasher: H) -> Self {
-        let (size_mask, hasher_number) = Self::fast_mask_and_hasher_size(capacity, Self::ABS_ERROR_RATE_LN);
+        let (size_mask, hasher_number) = fast_mask_and_hasher_size(capacity, ABS_ERROR_RATE_LN);
 
         Self { 
             capacity,
@@ -75,12 +70,13 @@ where
             hasher, 
             container: C::new(), 
             hasher_number, 
+            indexing: Indexing::Seeded,
             _marker: std::marker::PhantomData,
         }
     }
 
     pub fn with_all(capacity: usize, error_rate: f32, hasher: H) -> Self {
-        let (size_mask, hasher_number) = Self::mask_and_hasher_size(capacity, error_rate);
+        let (size_mask, hasher_number) = mask_and_hasher_size(capacity, error_rate);
 
         Self {
             capacity,
@@ -88,37 +84,174 @@ where
             hasher,
             container: C::new(),
             hasher_number,
+            indexing: Indexing::Seeded,
             _marker: std::marker::PhantomData,
         }
     }
 
-    pub fn contains(&self, value: &T) -> bool {
-        for k in 0..self.hasher_number {
-            if !self.mask.check(self.hasher.hash(value.as_bytes(), k) % self.mask.len()) {
-                return false;
+    /// Must be called before anything is inserted, since it changes which bits a value sets.
+    pub fn with_indexing(mut self, indexing: Indexing) -> Self {
+        assert!(self.mask.count_ones() == 0, "indexing of a non-empty filter cannot change");
+        self.indexing = indexing;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn indexing(&self) -> Indexing {
+        self.indexing
+    }
+
+    fn indexes<'a>(&'a self, bytes: &'a [u8]) -> Indexes<'a, H> {
+        Indexes::new(&self.hasher, bytes, self.indexing, self.hasher_number, self.mask.len())
+    }
+
+    /// Accepts any form the stored type borrows as, e.g. `&str` for `Bytes<String>` values.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + BloomKey + PartialEq,
+    {
+        let in_mask = value.with_key_bytes(|bytes| {
+            self.indexes(bytes).all(|index| self.mask.check(index))
+        });
+        in_mask && self.container.contains(value)
+    }
+
+    pub fn insert(&mut self, value: T) -> InsertOutcome {
+        if self.contains(&value) {
+            return InsertOutcome::ProbablyPresent;
+        }
+        if !self.is_empty() && (self.len() >= self.capacity) {
+            return InsertOutcome::CapacityReached;
+        }
+        value.with_key_bytes(|bytes| {
+            let bits = self.mask.len();
+            for index in Indexes::new(&self.hasher, bytes, self.indexing, self.hasher_number, bits) {
+                self.mask.update(index);
             }
+        });
+        self.container.insert(value);
+        InsertOutcome::Inserted
+    }
+
+    /// Returns `Ok(false)` if the value is probably present already.
+    pub fn try_insert(&mut self, value: T) -> Result<bool, InsertError> {
+        match self.insert(value) {
+            InsertOutcome::Inserted => Ok(true),
+            InsertOutcome::ProbablyPresent => Ok(false),
+            InsertOutcome::CapacityReached => Err(InsertError::Full),
         }
-        self.container.contains(value)
     }
+}
 
-    pub fn insert(&mut self, value: T) {
-        if self.is_empty() {
-            for k in 0..self.hasher_number {
-                self.mask.update(self.hasher.hash(value.as_bytes(), k) % self.mask.len());
-            }
-            self.container.insert(value);
-        } else if (self.len() < self.capacity) && (!self.contains(&value) || !self.container.contains(&value)){
-            for k in 0..self.hasher_number {
-                self.mask.update(self.hasher.hash(value.as_bytes(), k) % self.mask.len());
-            }
-            self.container.insert(value);
-        } 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub enum InsertOutcome {
+    Inserted,
+    ProbablyPresent,
+    /// The value was dropped because the filter already holds `capacity` values.
+    CapacityReached,
+}
+
+/// How the `k` bit positions of a value are derived.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Indexing {
+    /// One hash per position, each with its own seed.
+    #[default]
+    Seeded,
+    /// Kirsch–Mitzenmacher: position `i` is `h1 + i * h2`, so only two hashes are computed.
+    Double,
+    /// Like `Double`, but the step grows with `i`, which avoids repeated positions
+    /// when `h2` shares a factor with the mask size.
+    EnhancedDouble,
+}
+
+impl Indexing {
+    fn code(self) -> u16 {
+        match self {
+            Indexing::Seeded => 0,
+            Indexing::Double => 1,
+            Indexing::EnhancedDouble => 2,
+        }
+    }
+
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0 => Some(Indexing::Seeded),
+            1 => Some(Indexing::Double),
+            2 => Some(Indexing::EnhancedDouble),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) struct Indexes<'a, H> {
+    hasher: &'a H,
+    bytes: &'a [u8],
+    indexing: Indexing,
+    count: usize,
+    bits: usize,
+    k: usize,
+    position: usize,
+    step: usize,
+}
+
+impl<'a, H> Indexes<'a, H>
+where
+    H: HasherWithSeed,
+{
+    pub(crate) fn new(hasher: &'a H, bytes: &'a [u8], indexing: Indexing, count: usize, bits: usize) -> Self {
+        let (position, step) = match indexing {
+            Indexing::Seeded => (0, 0),
+            Indexing::Double | Indexing::EnhancedDouble => {
+                (hasher.hash(bytes, 0) % bits, (hasher.hash(bytes, 1) % bits).max(1))
+            },
+        };
+        Self { hasher, bytes, indexing, count, bits, k: 0, position, step }
+    }
+
+    // Both terms are below `bits`, so a subtraction replaces the division.
+    fn advance(&mut self) -> usize {
+        let index = self.position;
+        self.position += self.step;
+        if self.position >= self.bits {
+            self.position -= self.bits;
+        }
+        index
+    }
+}
+
+impl<H> Iterator for Indexes<'_, H>
+where
+    H: HasherWithSeed,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.k == self.count {
+            return None;
+        }
+        let index = match self.indexing {
+            Indexing::Seeded => self.hasher.hash(self.bytes, self.k) % self.bits,
+            Indexing::Double => self.advance(),
+            Indexing::EnhancedDouble => {
+                let index = self.advance();
+                self.step = (self.step + self.k + 1) % self.bits;
+                index
+            },
+        };
+        self.k += 1;
+        Some(index)
     }
 }
 
 impl<T, H, C> HasLength for BloomFilter<T, H, C>
 where 
-    T: Immutable + IntoBytes + PartialEq,
+    T: BloomKey + PartialEq,
     H: HasherWithSeed,
     C: Container<T>
 {
@@ -129,7 +262,7 @@ where
 
 impl<T> HasLength for Vec<T> {
     fn len(&self) -> usize {
-        Vec::<T>::len(&self)
+        Vec::<T>::len(self)
     }
 }
 
@@ -144,27 +277,98 @@ where T: PartialEq
         self.push(value);
     }
 
-    fn contains(&self, value: &T) -> bool {
-        self.as_slice().contains(value)
+    fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        self.iter().any(|stored| stored.borrow() == value)
     }
 }
 
 impl<T> Container<T> for Vec<T> where T: PartialEq{}
 
+/// A container that keeps no values, so membership is answered by the mask alone.
+/// Its length counts the insertions that set at least one new bit.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct NoStore<T> {
+    length: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> HasLength for NoStore<T> {
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+impl<T> Insertable<T> for NoStore<T> {
+    fn new() -> Self {
+        Self {
+            length: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn insert(&mut self, _value: T) {
+        self.length += 1;
+    }
+
+    fn contains<Q>(&self, _value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        true
+    }
+}
+
+impl<T> Container<T> for NoStore<T> {}
+
+/// A Bloom filter whose memory is just the bit array, e.g.
+/// `BitBloomFilter::with_all(capacity, 0.01, MurmurHash)`.
+pub type BitBloomFilter<T, H = MurmurHash> = BloomFilter<T, H, NoStore<T>>;
+
+pub(crate) const ABS_ERROR_RATE_LN: f32 = 2.9957323; // |ln(0.05)|
+
+pub(crate) fn fast_mask_and_hasher_size(capacity: usize, error_rate: f32) -> (usize, usize) {
+    let size_mask = (error_rate * (capacity as f32)) / (LN_2 * LN_2);
+    let k: usize = (error_rate / LN_2) as usize;
+
+    (size_mask as usize, if k > 0 { k } else { 1 })
+}
+
+pub(crate) fn mask_and_hasher_size(capacity: usize, error_rate: f32) -> (usize, usize) {
+    fast_mask_and_hasher_size(capacity, error_rate.ln().abs())
+}
+
 pub trait HasherWithSeed {
     fn hash(&self, data: &[u8], seed: usize) -> usize;
+
+    /// Identifies the hash function in serialized filters; zero means unidentified.
+    fn id(&self) -> u32 {
+        0
+    }
 }
 
+#[derive(Clone, Default)]
 pub struct MurmurHash;
 
 impl HasherWithSeed for MurmurHash {
     fn hash(&self, data: &[u8], seed: usize) -> usize {
+        // For short keys murmur only mixes `data ^ seed`, so consecutive seeds are spread first.
         if cfg!(target_pointer_width = "32") {
-            murmur2(data, seed as u32) as usize
+            murmur2(data, (seed as u32).wrapping_mul(0x9E37_79B9)) as usize
         } else {
-            murmur64a(data, seed as u64) as usize
+            murmur64a(data, (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)) as usize
         }
     }
+
+    fn id(&self) -> u32 {
+        if cfg!(target_pointer_width = "32") { 2 } else { 1 }
+    }
 }
 
 struct Mask {
@@ -188,9 +392,91 @@ impl Mask {
         self.bytes[byte_number] |= bit_number_in_byte;
     }
 
+    fn count_ones(&self) -> usize {
+        self.bytes.iter().map(|byte| byte.count_ones() as usize).sum()
+    }
+
     fn check(&self, bit_number: usize) -> bool {
-        let byte_number = bit_number >> 3;
-        let bit_number_in_byte = 0b1000_0000 >> (bit_number - (byte_number << 3));
-        (self.bytes[byte_number] & bit_number_in_byte).count_ones() == 1
+        check_bit(&self.bytes, bit_number)
+    }
+}
+
+fn check_bit(bytes: &[u8], bit_number: usize) -> bool {
+    let byte_number = bit_number >> 3;
+    let bit_number_in_byte = 0b1000_0000 >> (bit_number - (byte_number << 3));
+    (bytes[byte_number] & bit_number_in_byte).count_ones() == 1
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename = "BloomFilter")]
+struct SerializedBloomFilterRef<'a, C> {
+    capacity: usize,
+    hasher_id: u32,
+    hasher_number: usize,
+    indexing: Indexing,
+    mask: &'a [u8],
+    container: &'a C,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "BloomFilter")]
+struct SerializedBloomFilter<C> {
+    capacity: usize,
+    hasher_id: u32,
+    hasher_number: usize,
+    #[serde(default)]
+    indexing: Indexing,
+    mask: Vec<u8>,
+    container: C,
+}
+
+#[cfg(feature = "serde")]
+impl<T, H, C> serde::Serialize for BloomFilter<T, H, C>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed,
+    C: Container<T> + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedBloomFilterRef {
+            capacity: self.capacity,
+            hasher_id: self.hasher.id(),
+            hasher_number: self.hasher_number,
+            indexing: self.indexing,
+            mask: &self.mask.bytes,
+            container: &self.container,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, H, C> serde::Deserialize<'de> for BloomFilter<T, H, C>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed + Default,
+    C: Container<T> + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let filter = SerializedBloomFilter::<C>::deserialize(deserializer)?;
+        let hasher = H::default();
+        if filter.hasher_id != hasher.id() {
+            return Err(D::Error::custom("filter was serialized with a different hasher"));
+        }
+        if filter.mask.is_empty() || (filter.hasher_number == 0) {
+            return Err(D::Error::custom("filter has an empty mask or no hash functions"));
+        }
+        Ok(Self {
+            capacity: filter.capacity,
+            mask: Mask { bytes: filter.mask },
+            hasher,
+            container: filter.container,
+            hasher_number: filter.hasher_number,
+            indexing: filter.indexing,
+            _marker: std::marker::PhantomData,
+        })
     }
 }
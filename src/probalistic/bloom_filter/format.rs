@@ -0,0 +1,174 @@
+use std::io::{self, Read, Write};
+use murmur2::murmur64a;
+use zerocopy::{byteorder::little_endian::{U16, U32, U64}, FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+use crate::probalistic::{error::DecodeError, key::BloomKey};
+use super::{BloomFilter, HasherWithSeed, Indexing, Mask, NoStore};
+
+// Layout: header, mask bytes, then a little-endian u64 checksum of both.
+#[derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)]
+#[repr(C)]
+pub(super) struct Header {
+    magic: [u8; 4],
+    version: U16,
+    indexing: U16,
+    hasher_id: U32,
+    hasher_number: U32,
+    capacity: U64,
+    length: U64,
+    mask_length: U64,
+}
+
+const MAGIC: [u8; 4] = *b"DSBF";
+// Version 1 readers ignore `indexing`, so only `Seeded` filters are written as version 1.
+const SEEDED_VERSION: u16 = 1;
+const VERSION: u16 = 2;
+const CHECKSUM_SEED: u64 = 0x6473_6266;
+pub(super) const CHECKSUM_SIZE: usize = size_of::<u64>();
+
+impl Header {
+    pub(super) fn validate(&self, hasher_id: u32) -> Result<(), DecodeError> {
+        if self.magic != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+        match self.version.get() {
+            SEEDED_VERSION if self.indexing() != Some(Indexing::Seeded) => return Err(DecodeError::Corrupted),
+            SEEDED_VERSION | VERSION => {},
+            version => return Err(DecodeError::UnsupportedVersion(version)),
+        }
+        if self.hasher_id.get() != hasher_id {
+            return Err(DecodeError::HasherMismatch);
+        }
+        if (self.hasher_number.get() == 0) || (self.mask_length.get() == 0) || self.indexing().is_none() {
+            return Err(DecodeError::Corrupted);
+        }
+        Ok(())
+    }
+
+    pub(super) fn mask_length(&self) -> Result<usize, DecodeError> {
+        usize::try_from(self.mask_length.get()).map_err(|_| DecodeError::Corrupted)
+    }
+
+    pub(super) fn capacity(&self) -> Result<usize, DecodeError> {
+        usize::try_from(self.capacity.get()).map_err(|_| DecodeError::Corrupted)
+    }
+
+    pub(super) fn length(&self) -> Result<usize, DecodeError> {
+        usize::try_from(self.length.get()).map_err(|_| DecodeError::Corrupted)
+    }
+
+    pub(super) fn hasher_number(&self) -> usize {
+        self.hasher_number.get() as usize
+    }
+
+    // Files written before indexing was selectable hold zero here, which is `Seeded`.
+    pub(super) fn indexing(&self) -> Option<Indexing> {
+        Indexing::from_code(self.indexing.get())
+    }
+}
+
+// Borrows the header and mask from `bytes`, checking everything but the checksum unless `verify`.
+pub(super) fn parse(bytes: &[u8], hasher_id: u32, verify: bool) -> Result<(&Header, &[u8]), DecodeError> {
+    let (header, rest) = Header::ref_from_prefix(bytes).map_err(|_| DecodeError::Truncated)?;
+    header.validate(hasher_id)?;
+    let mask_length = header.mask_length()?;
+    if rest.len() < mask_length.saturating_add(CHECKSUM_SIZE) {
+        return Err(DecodeError::Truncated);
+    }
+    let (mask, rest) = rest.split_at(mask_length);
+    if verify {
+        verify_checksum(header, mask, rest[..CHECKSUM_SIZE].try_into().unwrap())?;
+    }
+    Ok((header, mask))
+}
+
+pub(super) fn checksum(header: &Header, mask: &[u8]) -> u64 {
+    murmur64a(mask, murmur64a(header.as_bytes(), CHECKSUM_SEED))
+}
+
+pub(super) fn verify_checksum(header: &Header, mask: &[u8], stored: [u8; CHECKSUM_SIZE]) -> Result<(), DecodeError> {
+    if checksum(header, mask) == u64::from_le_bytes(stored) {
+        Ok(())
+    } else {
+        Err(DecodeError::ChecksumMismatch)
+    }
+}
+
+impl<T, H> BloomFilter<T, H, NoStore<T>>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed,
+{
+    fn header(&self) -> Header {
+        Header {
+            magic: MAGIC,
+            version: U16::new(if self.indexing == Indexing::Seeded { SEEDED_VERSION } else { VERSION }),
+            indexing: U16::new(self.indexing.code()),
+            hasher_id: U32::new(self.hasher.id()),
+            hasher_number: U32::new(self.hasher_number as u32),
+            capacity: U64::new(self.capacity as u64),
+            length: U64::new(self.container.length as u64),
+            mask_length: U64::new(self.mask.bytes.len() as u64),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(size_of::<Header>() + self.mask.bytes.len() + CHECKSUM_SIZE);
+        self.write_to(&mut result).expect("writing to a Vec cannot fail");
+        result
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let header = self.header();
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(&self.mask.bytes)?;
+        writer.write_all(&checksum(&header, &self.mask.bytes).to_le_bytes())
+    }
+
+    /// `hasher` must be the hasher the filter was built with; its `id` is checked against the header.
+    pub fn from_bytes(bytes: &[u8], hasher: H) -> Result<Self, DecodeError> {
+        let (header, mask) = parse(bytes, hasher.id(), true)?;
+        Self::from_parts(header, mask.to_vec(), hasher)
+    }
+
+    pub fn read_from<R: Read>(mut reader: R, hasher: H) -> Result<Self, DecodeError> {
+        let mut header = Header::new_zeroed();
+        read_exact(&mut reader, header.as_mut_bytes())?;
+        header.validate(hasher.id())?;
+
+        // Grows with the bytes actually read, so a forged length cannot force a huge allocation.
+        let mask_length = header.mask_length()?;
+        let mut mask = vec![];
+        reader.by_ref().take(mask_length as u64).read_to_end(&mut mask).map_err(DecodeError::Io)?;
+        if mask.len() < mask_length {
+            return Err(DecodeError::Truncated);
+        }
+        let mut stored = [0; CHECKSUM_SIZE];
+        read_exact(&mut reader, &mut stored)?;
+        verify_checksum(&header, &mask, stored)?;
+
+        Self::from_parts(&header, mask, hasher)
+    }
+
+    fn from_parts(header: &Header, mask: Vec<u8>, hasher: H) -> Result<Self, DecodeError> {
+        Ok(Self {
+            capacity: header.capacity()?,
+            mask: Mask { bytes: mask },
+            hasher,
+            container: NoStore {
+                length: header.length()?,
+                _marker: std::marker::PhantomData,
+            },
+            hasher_number: header.hasher_number(),
+            indexing: header.indexing().ok_or(DecodeError::Corrupted)?,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), DecodeError> {
+    reader.read_exact(buffer).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => DecodeError::Truncated,
+        _ => DecodeError::Io(error),
+    })
+}
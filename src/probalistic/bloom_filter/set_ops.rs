@@ -0,0 +1,113 @@
+use crate::{container::Container, probalistic::{error::MergeError, key::BloomKey}};
+use super::{BloomFilter, HasherWithSeed, NoStore};
+
+impl<T, H, C> BloomFilter<T, H, C>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed,
+    C: Container<T>,
+{
+    fn check_compatible<D: Container<T>>(&self, other: &BloomFilter<T, H, D>) -> Result<(), MergeError> {
+        if self.mask.bytes.len() != other.mask.bytes.len() {
+            Err(MergeError::SizeMismatch)
+        } else if self.hasher_number != other.hasher_number {
+            Err(MergeError::HasherCountMismatch)
+        } else if self.hasher.id() != other.hasher.id() {
+            Err(MergeError::HasherMismatch)
+        } else if self.indexing != other.indexing {
+            Err(MergeError::IndexingMismatch)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Swamidass & Baldi estimate of how many values set `ones` bits.
+    fn estimate_from_ones(&self, ones: usize) -> f64 {
+        let bits = self.mask.len() as f64;
+        let ones = (ones as f64).min(bits - 1.0);
+        -(bits / self.hasher_number as f64) * (1.0 - ones / bits).ln()
+    }
+
+    pub fn estimate_len(&self) -> f64 {
+        self.estimate_from_ones(self.mask.count_ones())
+    }
+
+    fn union_ones<D: Container<T>>(&self, other: &BloomFilter<T, H, D>) -> usize {
+        self.mask.bytes.iter()
+            .zip(&other.mask.bytes)
+            .map(|(first, second)| (first | second).count_ones() as usize)
+            .sum()
+    }
+
+    pub fn estimate_intersection_size<D: Container<T>>(&self, other: &BloomFilter<T, H, D>) -> Result<f64, MergeError> {
+        self.check_compatible(other)?;
+        let union = self.estimate_from_ones(self.union_ones(other));
+        Ok((self.estimate_len() + other.estimate_len() - union).max(0.0))
+    }
+
+    pub fn jaccard<D: Container<T>>(&self, other: &BloomFilter<T, H, D>) -> Result<f64, MergeError> {
+        let intersection = self.estimate_intersection_size(other)?;
+        let union = self.estimate_from_ones(self.union_ones(other));
+        Ok(if union > 0.0 { (intersection / union).min(1.0) } else { 1.0 })
+    }
+
+    fn union_mask<D: Container<T>>(&mut self, other: &BloomFilter<T, H, D>) {
+        for (byte, other) in self.mask.bytes.iter_mut().zip(&other.mask.bytes) {
+            *byte |= other;
+        }
+    }
+
+    fn intersect_mask<D: Container<T>>(&mut self, other: &BloomFilter<T, H, D>) {
+        for (byte, other) in self.mask.bytes.iter_mut().zip(&other.mask.bytes) {
+            *byte &= other;
+        }
+    }
+}
+
+impl<T, H> BloomFilter<T, H, NoStore<T>>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed,
+{
+    /// Afterwards `len` is the estimated number of distinct values in either filter.
+    pub fn union(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        self.union_mask(other);
+        self.container.length = self.estimate_len().round() as usize;
+        Ok(())
+    }
+
+    /// The result may report more false positives than a filter built from the intersection.
+    pub fn intersect(&mut self, other: &Self) -> Result<(), MergeError> {
+        let length = self.estimate_intersection_size(other)?;
+        self.intersect_mask(other);
+        self.container.length = length.round() as usize;
+        Ok(())
+    }
+}
+
+impl<T, H> BloomFilter<T, H, Vec<T>>
+where
+    T: BloomKey + PartialEq + Clone,
+    H: HasherWithSeed,
+{
+    /// Values of `other` missing here are cloned in, even past `capacity`.
+    pub fn union(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        self.union_mask(other);
+        for value in &other.container {
+            if !self.container.contains(value) {
+                self.container.push(value.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the values stored in both filters.
+    pub fn intersect(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        self.intersect_mask(other);
+        self.container.retain(|value| other.container.contains(value));
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{container::HasLength, probalistic::{error::DecodeError, key::BloomKey}};
+use super::{check_bit, format::parse, HasherWithSeed, Indexes, Indexing, MurmurHash};
+
+/// A read-only filter over bytes written by [`BitBloomFilter::to_bytes`](super::BitBloomFilter::to_bytes),
+/// e.g. a memory-mapped file. The bit array is borrowed, not copied.
+pub struct BloomFilterView<'a, T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    mask: &'a [u8],
+    hasher: H,
+    hasher_number: usize,
+    indexing: Indexing,
+    capacity: usize,
+    length: usize,
+    _marker: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<'a, T, H> BloomFilterView<'a, T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    /// `hasher` must be the hasher the filter was built with; its `id` is checked against the header.
+    pub fn new(bytes: &'a [u8], hasher: H) -> Result<Self, DecodeError> {
+        Self::parse(bytes, hasher, true)
+    }
+
+    /// Like [`new`](Self::new), but skips the checksum, which reads the whole bit array.
+    pub fn new_unverified(bytes: &'a [u8], hasher: H) -> Result<Self, DecodeError> {
+        Self::parse(bytes, hasher, false)
+    }
+
+    fn parse(bytes: &'a [u8], hasher: H, verify: bool) -> Result<Self, DecodeError> {
+        let (header, mask) = parse(bytes, hasher.id(), verify)?;
+        Ok(Self {
+            mask,
+            hasher_number: header.hasher_number(),
+            indexing: header.indexing().ok_or(DecodeError::Corrupted)?,
+            capacity: header.capacity()?,
+            length: header.length()?,
+            hasher,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn indexing(&self) -> Indexing {
+        self.indexing
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        value.with_key_bytes(|bytes| {
+            Indexes::new(&self.hasher, bytes, self.indexing, self.hasher_number, self.mask.len() << 3)
+                .all(|index| check_bit(self.mask, index))
+        })
+    }
+}
+
+impl<T, H> HasLength for BloomFilterView<'_, T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
\ No newline at end of file
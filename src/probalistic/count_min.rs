@@ -0,0 +1,95 @@
+use std::f64::consts::E;
+
+use crate::probalistic::{bloom_filter::{HasherWithSeed, MurmurHash}, error::MergeError, key::BloomKey};
+
+pub struct CountMinSketch<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    width: usize,
+    depth: usize,
+    counters: Vec<u64>,
+    hasher: H,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> CountMinSketch<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_error(epsilon: f64, delta: f64) -> Self {
+        Self::with_hasher(epsilon, delta, MurmurHash)
+    }
+}
+
+impl<T, H> CountMinSketch<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    /// Estimates exceed the true count by at most `epsilon` times the total count
+    /// with probability at least `1 - delta`.
+    pub fn with_hasher(epsilon: f64, delta: f64, hasher: H) -> Self {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+        assert!((delta > 0.0) && (delta < 1.0), "delta must be in (0, 1)");
+        let width = (E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil() as usize;
+        Self::with_size(width, depth, hasher)
+    }
+
+    pub fn with_size(width: usize, depth: usize, hasher: H) -> Self {
+        let width = width.max(1);
+        let depth = depth.max(1);
+        Self {
+            width,
+            depth,
+            counters: vec![0; width.checked_mul(depth).expect("sketch size overflows usize")],
+            hasher,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn index(&self, bytes: &[u8], row: usize) -> usize {
+        row * self.width + self.hasher.hash(bytes, row) % self.width
+    }
+
+    pub fn add(&mut self, value: &T, count: u64) {
+        value.with_key_bytes(|bytes| {
+            for row in 0..self.depth {
+                let index = self.index(bytes, row);
+                self.counters[index] = self.counters[index].saturating_add(count);
+            }
+        });
+    }
+
+    pub fn estimate(&self, value: &T) -> u64 {
+        value.with_key_bytes(|bytes| {
+            (0..self.depth)
+                .map(|row| self.counters[self.index(bytes, row)])
+                .min()
+                .unwrap_or(0)
+        })
+    }
+
+    /// Both sketches must have been built with the same dimensions and hasher.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        if (self.width != other.width) || (self.depth != other.depth) {
+            return Err(MergeError::SizeMismatch);
+        }
+        if self.hasher.id() != other.hasher.id() {
+            return Err(MergeError::HasherMismatch);
+        }
+        for (counter, other) in self.counters.iter_mut().zip(&other.counters) {
+            *counter = counter.saturating_add(*other);
+        }
+        Ok(())
+    }
+}
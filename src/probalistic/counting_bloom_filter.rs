@@ -0,0 +1,145 @@
+use crate::{container::HasLength, probalistic::{bloom_filter::{fast_mask_and_hasher_size, mask_and_hasher_size, HasherWithSeed, MurmurHash, ABS_ERROR_RATE_LN}, key::BloomKey}};
+
+pub struct CountingBloomFilter<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    counters: Counters,
+    hasher: H,
+    hasher_number: usize,
+    length: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> CountingBloomFilter<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_hasher(capacity, MurmurHash)
+    }
+
+    pub fn with_error_rate(capacity: usize, error_rate: f32) -> Self {
+        Self::with_all(capacity, error_rate, MurmurHash)
+    }
+}
+
+impl<T, H> CountingBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    pub fn with_hasher(capacity: usize, hasher: H) -> Self {
+        let (size_mask, hasher_number) = fast_mask_and_hasher_size(capacity, ABS_ERROR_RATE_LN);
+        Self::with_sizes(size_mask, hasher_number, hasher)
+    }
+
+    pub fn with_all(capacity: usize, error_rate: f32, hasher: H) -> Self {
+        let (size_mask, hasher_number) = mask_and_hasher_size(capacity, error_rate);
+        Self::with_sizes(size_mask, hasher_number, hasher)
+    }
+
+    fn with_sizes(size_mask: usize, hasher_number: usize, hasher: H) -> Self {
+        Self {
+            counters: Counters::with_size(size_mask),
+            hasher,
+            hasher_number,
+            length: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn index(&self, bytes: &[u8], k: usize) -> usize {
+        self.hasher.hash(bytes, k) % self.counters.len()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        value.with_key_bytes(|bytes| {
+            (0..self.hasher_number).all(|k| self.counters.get(self.index(bytes, k)) > 0)
+        })
+    }
+
+    pub fn insert(&mut self, value: &T) {
+        value.with_key_bytes(|bytes| {
+            for k in 0..self.hasher_number {
+                let index = self.index(bytes, k);
+                self.counters.increment(index);
+            }
+        });
+        self.length += 1;
+    }
+
+    /// Returns `false` and leaves the filter untouched if `value` is definitely absent.
+    /// Saturated counters never go down, so an empty filter is checked by its length instead.
+    pub fn remove(&mut self, value: &T) -> bool {
+        if (self.length == 0) || !self.contains(value) {
+            return false;
+        }
+        value.with_key_bytes(|bytes| {
+            for k in 0..self.hasher_number {
+                let index = self.index(bytes, k);
+                self.counters.decrement(index);
+            }
+        });
+        self.length -= 1;
+        true
+    }
+}
+
+impl<T, H> HasLength for CountingBloomFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+// Packs two 4-bit counters per byte. A counter that reaches the maximum stays there,
+// because the number of values sharing it is no longer known.
+struct Counters {
+    bytes: Vec<u8>,
+}
+
+impl Counters {
+    const MAX: u8 = 0b1111;
+
+    fn with_size(size: usize) -> Self {
+        Self {
+            bytes: vec![0; (size >> 1) + 1]
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bytes.len() << 1
+    }
+
+    fn shift(counter_number: usize) -> u32 {
+        ((counter_number & 1) << 2) as u32
+    }
+
+    fn get(&self, counter_number: usize) -> u8 {
+        (self.bytes[counter_number >> 1] >> Self::shift(counter_number)) & Self::MAX
+    }
+
+    fn set(&mut self, counter_number: usize, value: u8) {
+        let shift = Self::shift(counter_number);
+        let byte = &mut self.bytes[counter_number >> 1];
+        *byte = (*byte & !(Self::MAX << shift)) | (value << shift);
+    }
+
+    fn increment(&mut self, counter_number: usize) {
+        let value = self.get(counter_number);
+        if value < Self::MAX {
+            self.set(counter_number, value + 1);
+        }
+    }
+
+    fn decrement(&mut self, counter_number: usize) {
+        let value = self.get(counter_number);
+        if (value > 0) && (value < Self::MAX) {
+            self.set(counter_number, value - 1);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use zerocopy::IntoBytes;
+
+use crate::{container::HasLength, probalistic::{bloom_filter::{HasherWithSeed, MurmurHash}, error::InsertError, key::BloomKey}};
+
+/// Stores 16-bit fingerprints in buckets of four, giving a false positive rate of about 0.01%.
+pub struct CuckooFilter<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    buckets: Vec<u16>,
+    bucket_mask: usize,
+    length: usize,
+    hasher: H,
+    random_state: u64,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> CuckooFilter<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_hasher(capacity, MurmurHash)
+    }
+}
+
+impl<T, H> CuckooFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    const BUCKET_SIZE: usize = 4;
+    const MAX_KICKS: usize = 500;
+    const LOAD_FACTOR: f64 = 0.95;
+
+    pub fn with_hasher(capacity: usize, hasher: H) -> Self {
+        let slots = (capacity as f64 / Self::LOAD_FACTOR).ceil() as usize;
+        let bucket_number = slots.div_ceil(Self::BUCKET_SIZE).next_power_of_two();
+        Self {
+            buckets: vec![0; bucket_number * Self::BUCKET_SIZE],
+            bucket_mask: bucket_number - 1,
+            length: 0,
+            hasher,
+            random_state: 0x2545_F491_4F6C_DD1D,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buckets.len()
+    }
+
+    // Zero marks an empty slot, so it is never used as a fingerprint.
+    fn fingerprint_and_index(&self, value: &T) -> (u16, usize) {
+        let hash = value.with_key_bytes(|bytes| self.hasher.hash(bytes, 0));
+        let fingerprint = (hash >> (usize::BITS - 16)) as u16;
+        (fingerprint.max(1), hash & self.bucket_mask)
+    }
+
+    fn alt_index(&self, index: usize, fingerprint: u16) -> usize {
+        (index ^ self.hasher.hash(fingerprint.as_bytes(), 1)) & self.bucket_mask
+    }
+
+    fn bucket(&self, index: usize) -> &[u16] {
+        &self.buckets[index * Self::BUCKET_SIZE..(index + 1) * Self::BUCKET_SIZE]
+    }
+
+    fn bucket_mut(&mut self, index: usize) -> &mut [u16] {
+        &mut self.buckets[index * Self::BUCKET_SIZE..(index + 1) * Self::BUCKET_SIZE]
+    }
+
+    fn try_put(&mut self, index: usize, fingerprint: u16) -> bool {
+        match self.bucket_mut(index).iter_mut().find(|slot| **slot == 0) {
+            Some(slot) => {
+                *slot = fingerprint;
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn next_random(&mut self) -> usize {
+        self.random_state ^= self.random_state << 13;
+        self.random_state ^= self.random_state >> 7;
+        self.random_state ^= self.random_state << 17;
+        self.random_state as usize
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let (fingerprint, index) = self.fingerprint_and_index(value);
+        self.bucket(index).contains(&fingerprint)
+            || self.bucket(self.alt_index(index, fingerprint)).contains(&fingerprint)
+    }
+
+    /// Inserting the same value twice stores two fingerprints, so it must be removed twice.
+    pub fn insert(&mut self, value: &T) -> Result<(), InsertError> {
+        let (fingerprint, index) = self.fingerprint_and_index(value);
+        let alt_index = self.alt_index(index, fingerprint);
+        if self.try_put(index, fingerprint) || self.try_put(alt_index, fingerprint) {
+            self.length += 1;
+            return Ok(());
+        }
+
+        // Evicted slots are recorded so that a failed insert leaves the filter unchanged.
+        let mut evictions = Vec::with_capacity(Self::MAX_KICKS);
+        let mut index = if self.next_random() & 1 == 0 { index } else { alt_index };
+        let mut fingerprint = fingerprint;
+        for _ in 0..Self::MAX_KICKS {
+            let slot = index * Self::BUCKET_SIZE + self.next_random() % Self::BUCKET_SIZE;
+            evictions.push(slot);
+            std::mem::swap(&mut fingerprint, &mut self.buckets[slot]);
+
+            index = self.alt_index(index, fingerprint);
+            if self.try_put(index, fingerprint) {
+                self.length += 1;
+                return Ok(());
+            }
+        }
+
+        for slot in evictions.into_iter().rev() {
+            std::mem::swap(&mut fingerprint, &mut self.buckets[slot]);
+        }
+        Err(InsertError::Full)
+    }
+
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (fingerprint, index) = self.fingerprint_and_index(value);
+        let alt_index = self.alt_index(index, fingerprint);
+        for index in [index, alt_index] {
+            if let Some(slot) = self.bucket_mut(index).iter_mut().find(|slot| **slot == fingerprint) {
+                *slot = 0;
+                self.length -= 1;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<T, H> HasLength for CuckooFilter<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    fn len(&self) -> usize {
+        self.length
+    }
+}
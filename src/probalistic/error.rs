@@ -0,0 +1,77 @@
+use std::{fmt, io};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeError {
+    SizeMismatch,
+    HasherCountMismatch,
+    HasherMismatch,
+    IndexingMismatch,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::SizeMismatch => write!(f, "structures have different sizes"),
+            MergeError::HasherCountMismatch => write!(f, "structures use different numbers of hash functions"),
+            MergeError::HasherMismatch => write!(f, "structures use different hashers"),
+            MergeError::IndexingMismatch => write!(f, "structures derive bit positions differently"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertError {
+    Full,
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Full => write!(f, "structure is full"),
+        }
+    }
+}
+
+impl std::error::Error for InsertError {}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u16),
+    HasherMismatch,
+    ChecksumMismatch,
+    Corrupted,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(error) => write!(f, "i/o error: {error}"),
+            DecodeError::Truncated => write!(f, "data is truncated"),
+            DecodeError::BadMagic => write!(f, "data does not start with a known header"),
+            DecodeError::UnsupportedVersion(version) => write!(f, "format version {version} is not supported"),
+            DecodeError::HasherMismatch => write!(f, "data was written with a different hasher"),
+            DecodeError::ChecksumMismatch => write!(f, "checksum does not match"),
+            DecodeError::Corrupted => write!(f, "header fields are inconsistent"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(error: io::Error) -> Self {
+        DecodeError::Io(error)
+    }
+}
@@ -0,0 +1,131 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::probalistic::bloom_filter::HasherWithSeed;
+
+// Hashes are truncated to `usize`, so 32-bit targets get their own ids.
+fn native_id(id: u32) -> u32 {
+    if cfg!(target_pointer_width = "32") { id | 0x100 } else { id }
+}
+
+fn spread(seed: usize) -> u64 {
+    (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+fn fmix64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+    hash ^ (hash >> 33)
+}
+
+/// xxHash64; with seed 0 the result matches other xxHash64 implementations.
+#[cfg(feature = "xxhash")]
+#[derive(Clone, Default)]
+pub struct XxHash64;
+
+#[cfg(feature = "xxhash")]
+impl HasherWithSeed for XxHash64 {
+    fn hash(&self, data: &[u8], seed: usize) -> usize {
+        xxhash_rust::xxh64::xxh64(data, seed as u64) as usize
+    }
+
+    fn id(&self) -> u32 {
+        native_id(3)
+    }
+}
+
+/// XXH3 64-bit; with seed 0 the result matches other XXH3 implementations.
+#[cfg(feature = "xxhash")]
+#[derive(Clone, Default)]
+pub struct Xxh3;
+
+#[cfg(feature = "xxhash")]
+impl HasherWithSeed for Xxh3 {
+    fn hash(&self, data: &[u8], seed: usize) -> usize {
+        xxhash_rust::xxh3::xxh3_64_with_seed(data, seed as u64) as usize
+    }
+
+    fn id(&self) -> u32 {
+        native_id(4)
+    }
+}
+
+/// 64-bit FNV-1a followed by the murmur3 finalizer. Cheap for short keys, but easy to attack
+/// with chosen inputs.
+#[derive(Clone, Default)]
+pub struct FnvHash;
+
+impl FnvHash {
+    const OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01B3;
+}
+
+impl HasherWithSeed for FnvHash {
+    fn hash(&self, data: &[u8], seed: usize) -> usize {
+        let hash = data.iter().fold(Self::OFFSET_BASIS ^ spread(seed), |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(Self::PRIME)
+        });
+        // Multiplication only carries upwards, so the low bits that pick a filter position
+        // would ignore most of the input without a final mix.
+        fmix64(hash) as usize
+    }
+
+    fn id(&self) -> u32 {
+        native_id(5)
+    }
+}
+
+/// SipHash-2-4 under a secret key, for filters that hold user-controlled values.
+/// The key is not serialized, so a loaded filter needs the same key again. The `id` carries a
+/// 16-bit fingerprint of the key, so a wrong key is rejected on load or merge unless the
+/// fingerprints collide, which happens for 1 in 65536 keys.
+#[cfg(feature = "siphash")]
+#[derive(Clone)]
+pub struct SipHash {
+    key0: u64,
+    key1: u64,
+}
+
+#[cfg(feature = "siphash")]
+impl SipHash {
+    pub fn with_keys(key0: u64, key1: u64) -> Self {
+        Self { key0, key1 }
+    }
+
+    /// Uses a key drawn from the randomness behind `std::collections::HashMap`.
+    pub fn random() -> Self {
+        let state = std::collections::hash_map::RandomState::new();
+        Self::with_keys(state.hash_one(0u8), state.hash_one(1u8))
+    }
+}
+
+#[cfg(feature = "siphash")]
+impl HasherWithSeed for SipHash {
+    fn hash(&self, data: &[u8], seed: usize) -> usize {
+        siphasher::sip::SipHasher24::new_with_keys(self.key0 ^ spread(seed), self.key1).hash(data) as usize
+    }
+
+    // The top bits of a hash under the key reveal nothing useful about the key itself.
+    fn id(&self) -> u32 {
+        let fingerprint = siphasher::sip::SipHasher24::new_with_keys(self.key0, self.key1).hash(b"key fingerprint") >> 48;
+        native_id(6) | ((fingerprint as u32) << 16)
+    }
+}
+
+/// Turns any [`BuildHasher`] into a [`HasherWithSeed`] by feeding the seed before the data.
+/// Its `id` is zero, since the wrapped hasher cannot be identified.
+#[derive(Clone, Default)]
+pub struct BuildHasherAdapter<B: BuildHasher>(pub B);
+
+impl<B> HasherWithSeed for BuildHasherAdapter<B>
+where
+    B: BuildHasher,
+{
+    fn hash(&self, data: &[u8], seed: usize) -> usize {
+        let mut hasher = self.0.build_hasher();
+        hasher.write_usize(seed);
+        hasher.write(data);
+        hasher.finish() as usize
+    }
+}
\ No newline at end of file
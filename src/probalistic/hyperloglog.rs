@@ -0,0 +1,95 @@
+use crate::probalistic::{bloom_filter::{HasherWithSeed, MurmurHash}, error::MergeError, key::BloomKey};
+
+pub struct HyperLogLog<T, H: HasherWithSeed = MurmurHash>
+where
+    T: ?Sized + BloomKey
+{
+    precision: u32,
+    registers: Vec<u8>,
+    hasher: H,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> HyperLogLog<T, MurmurHash>
+where
+    T: ?Sized + BloomKey,
+{
+    pub fn with_precision(precision: u32) -> Self {
+        Self::with_hasher(precision, MurmurHash)
+    }
+}
+
+impl<T, H> HyperLogLog<T, H>
+where
+    T: ?Sized + BloomKey,
+    H: HasherWithSeed,
+{
+    pub const MIN_PRECISION: u32 = 4;
+    pub const MAX_PRECISION: u32 = 18;
+
+    /// Uses `2^precision` one-byte registers; the standard error is about `1.04 / sqrt(2^precision)`.
+    pub fn with_hasher(precision: u32, hasher: H) -> Self {
+        assert!(
+            (Self::MIN_PRECISION..=Self::MAX_PRECISION).contains(&precision),
+            "precision must be in {}..={}", Self::MIN_PRECISION, Self::MAX_PRECISION
+        );
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+            hasher,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    pub fn insert(&mut self, value: &T) {
+        let hash = value.with_key_bytes(|bytes| self.hasher.hash(bytes, 0));
+        let index = hash >> (usize::BITS - self.precision);
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = (rest.leading_zeros() + 1) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&register| (-(register as f64)).exp2()).sum();
+        let raw = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|&&register| register == 0).count();
+        if (raw <= 2.5 * m) && (zeros > 0) {
+            return m * (m / zeros as f64).ln();
+        }
+
+        // Only a 32-bit hash saturates at realistic cardinalities.
+        let hash_space = (usize::BITS as f64).exp2();
+        if (usize::BITS == 32) && (raw > hash_space / 30.0) {
+            return -hash_space * (1.0 - raw / hash_space).ln();
+        }
+        raw
+    }
+
+    /// Both estimators must have been built with the same precision and hasher.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        if self.precision != other.precision {
+            return Err(MergeError::SizeMismatch);
+        }
+        if self.hasher.id() != other.hasher.id() {
+            return Err(MergeError::HasherMismatch);
+        }
+        for (register, &other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::{borrow::Borrow, hash::{Hash, Hasher}};
+use zerocopy::{Immutable, IntoBytes};
+
+/// Supplies the bytes that the probabilistic structures hash for a value.
+/// Zerocopy types, `str` and `[u8]` use their memory directly; other types go through [`Bytes`] or [`Hashed`].
+pub trait BloomKey {
+    fn with_key_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R;
+}
+
+impl<T> BloomKey for T
+where
+    T: ?Sized + Immutable + IntoBytes,
+{
+    fn with_key_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(self.as_bytes())
+    }
+}
+
+/// Keys a value by the bytes it exposes, e.g. `Bytes(String::from("key"))` or `Bytes(&b"key"[..])`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Bytes<T>(pub T);
+
+impl<T> BloomKey for Bytes<T>
+where
+    T: AsRef<[u8]>,
+{
+    fn with_key_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(self.0.as_ref())
+    }
+}
+
+// Lets filters of owned keys be queried without allocating; both forms hash the same bytes.
+impl Borrow<str> for Bytes<String> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<[u8]> for Bytes<Vec<u8>> {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Keys a value by the data its [`Hash`] implementation writes, which the filter's hasher then mixes.
+/// That data may change between Rust versions and platforms, so filters keyed this way should not
+/// be stored or shared across builds.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Hashed<T>(pub T);
+
+impl<T> BloomKey for Hashed<T>
+where
+    T: Hash,
+{
+    fn with_key_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        let mut collector = ByteCollector(Vec::new());
+        self.0.hash(&mut collector);
+        f(&collector.0)
+    }
+}
+
+struct ByteCollector(Vec<u8>);
+
+impl Hasher for ByteCollector {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
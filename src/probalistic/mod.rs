@@ -1,2 +1,12 @@
+pub mod atomic_bloom_filter;
+pub mod blocked_bloom_filter;
 pub mod bloom_filter;
+pub mod count_min;
+pub mod counting_bloom_filter;
+pub mod cuckoo_filter;
+pub mod error;
+pub mod hashers;
+pub mod hyperloglog;
+pub mod key;
+pub mod scalable_bloom_filter;
 pub use zerocopy::{IntoBytes, Immutable};
\ No newline at end of file
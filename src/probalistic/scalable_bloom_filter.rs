@@ -0,0 +1,88 @@
+use std::borrow::Borrow;
+
+use crate::{container::{Container, HasLength}, probalistic::{bloom_filter::{BloomFilter, HasherWithSeed, InsertOutcome, MurmurHash}, key::BloomKey}};
+
+/// Adds a new [`BloomFilter`] layer whenever the last one is full. Each layer doubles the capacity
+/// and halves the error rate, so the combined false positive rate stays below the configured one.
+pub struct ScalableBloomFilter<T, H: HasherWithSeed + Clone = MurmurHash, C: Container<T> = Vec<T>>
+where
+    T: BloomKey + PartialEq
+{
+    layers: Vec<BloomFilter<T, H, C>>,
+    error_rate: f32,
+    hasher: H,
+}
+
+impl<T> ScalableBloomFilter<T, MurmurHash, Vec<T>>
+where
+    T: BloomKey + PartialEq,
+{
+    pub fn with_capacity(initial_capacity: usize) -> Self {
+        Self::with_hasher(initial_capacity, MurmurHash)
+    }
+
+    pub fn with_error_rate(initial_capacity: usize, error_rate: f32) -> Self {
+        Self::with_all(initial_capacity, error_rate, MurmurHash)
+    }
+}
+
+impl<T, H, C> ScalableBloomFilter<T, H, C>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed + Clone,
+    C: Container<T>,
+{
+    const GROWTH: usize = 2;
+    const TIGHTENING: f32 = 0.5;
+    const DEFAULT_ERROR_RATE: f32 = 0.05;
+
+    pub fn with_hasher(initial_capacity: usize, hasher: H) -> Self {
+        Self::with_all(initial_capacity, Self::DEFAULT_ERROR_RATE, hasher)
+    }
+
+    pub fn with_all(initial_capacity: usize, error_rate: f32, hasher: H) -> Self {
+        let first_error_rate = error_rate * (1.0 - Self::TIGHTENING);
+        Self {
+            layers: vec![BloomFilter::with_all(initial_capacity.max(1), first_error_rate, hasher.clone())],
+            error_rate: first_error_rate,
+            hasher,
+        }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + BloomKey + PartialEq,
+    {
+        self.layers.iter().any(|layer| layer.contains(value))
+    }
+
+    /// Never reports [`InsertOutcome::CapacityReached`], since a full layer gets a successor.
+    pub fn insert(&mut self, value: T) -> InsertOutcome {
+        if self.contains(&value) {
+            return InsertOutcome::ProbablyPresent;
+        }
+        let last = self.layers.last().unwrap();
+        if last.len() >= last.capacity() {
+            let capacity = last.capacity() * Self::GROWTH;
+            self.error_rate *= Self::TIGHTENING;
+            self.layers.push(BloomFilter::with_all(capacity, self.error_rate, self.hasher.clone()));
+        }
+        self.layers.last_mut().unwrap().insert(value)
+    }
+}
+
+impl<T, H, C> HasLength for ScalableBloomFilter<T, H, C>
+where
+    T: BloomKey + PartialEq,
+    H: HasherWithSeed + Clone,
+    C: Container<T>,
+{
+    fn len(&self) -> usize {
+        self.layers.iter().map(|layer| layer.len()).sum()
+    }
+}